@@ -3,37 +3,231 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
-///! Functions to compute various statistics on a slice of
-///! floating-point numbers.
+//! Functions to compute various statistics on a slice of
+//! floating-point numbers.
+//!
+//! The crate is `no_std` without its default `std` feature.
+//! Statistics that need to allocate are then available with
+//! the `alloc` feature, and those that need floating-point
+//! functions beyond the square root require `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
 
 /// Type of statistics function. If the statistic
 /// is ill-defined, `None` will be returned.
 pub type StatFn = fn(&[f64]) -> Option<f64>;
 
+/// Type of boxed statistics function, which unlike
+/// `StatFn` may be a closure capturing its configuration.
+#[cfg(feature = "alloc")]
+pub type BoxedStatFn = alloc::boxed::Box<dyn Fn(&[f64]) -> Option<f64>>;
+
+/// Floating-point types accepted by the generic statistics
+/// functions. Implemented for `f32` and `f64`.
+pub trait Float:
+    Copy
+    + PartialOrd
+    + core::ops::Add<Output = Self>
+    + core::ops::Sub<Output = Self>
+    + core::ops::Mul<Output = Self>
+    + core::ops::Div<Output = Self>
+    + core::ops::AddAssign
+{
+    /// The value 0.0.
+    fn zero() -> Self;
+    /// The value 1.0.
+    fn one() -> Self;
+    /// Nearest value to the count `n`.
+    fn from_usize(n: usize) -> Self;
+    /// Square root.
+    fn sqrt(self) -> Self;
+    /// Absolute value.
+    fn abs(self) -> Self;
+    /// Whether the value is `NaN`.
+    fn is_nan(self) -> bool;
+    /// Minimum of two values, ignoring `NaN`.
+    fn min(self, other: Self) -> Self;
+    /// Maximum of two values, ignoring `NaN`.
+    fn max(self, other: Self) -> Self;
+}
+
+macro_rules! impl_float {
+    ($t:ident) => {
+        impl Float for $t {
+            fn zero() -> Self {
+                0.0
+            }
+            fn one() -> Self {
+                1.0
+            }
+            fn from_usize(n: usize) -> Self {
+                n as $t
+            }
+            #[cfg(any(feature = "std", test))]
+            fn sqrt(self) -> Self {
+                $t::sqrt(self)
+            }
+            #[cfg(not(any(feature = "std", test)))]
+            fn sqrt(self) -> Self {
+                soft_sqrt(self as f64) as $t
+            }
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+            fn is_nan(self) -> bool {
+                $t::is_nan(self)
+            }
+            fn min(self, other: Self) -> Self {
+                $t::min(self, other)
+            }
+            fn max(self, other: Self) -> Self {
+                $t::max(self, other)
+            }
+        }
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+/// Correctly rounded square root by integer arithmetic, for
+/// builds without the standard library's floating-point
+/// functions. Being exact, it agrees with `f64::sqrt` even
+/// when another crate brings the standard library in.
+#[cfg(any(not(feature = "std"), test))]
+fn soft_sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    }
+    if x == 0.0 || x == f64::INFINITY {
+        return x;
+    }
+    // Write x as mant * 2^exp, with mant an integer.
+    let bits = x.to_bits();
+    let fraction = bits & ((1 << 52) - 1);
+    let biased = (bits >> 52) as i32;
+    let (mant, exp) = if biased == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased - 1075)
+    };
+    // Widen mant by an even shift, keeping exp even, so the
+    // integer root carries bits to spare for rounding.
+    let mut shift = 111 - (64 - mant.leading_zeros() as i32);
+    if (exp - shift) % 2 != 0 {
+        shift += 1;
+    }
+    let wide = u128::from(mant) << shift;
+    let mut root = wide.isqrt();
+    // A sticky bit below the rounding position records any
+    // remainder, so the conversion rounds as for the exact
+    // root.
+    if root * root != wide {
+        root |= 1;
+    }
+    let scale = f64::from_bits((((exp - shift) / 2 + 1023) as u64) << 52);
+    root as f64 * scale
+}
+
+#[test]
+fn test_soft_sqrt_matches() {
+    for &x in &[
+        0.0,
+        5e-324,
+        1e-310,
+        0.25,
+        2.0,
+        10.0,
+        12345.678,
+        1e300,
+        f64::MAX,
+    ] {
+        assert_eq!(x.sqrt(), soft_sqrt(x));
+    }
+    let mut x = 1e-300;
+    while x < 1e300 {
+        assert_eq!(x.sqrt(), soft_sqrt(x));
+        x *= 1.000_7;
+    }
+    assert!(soft_sqrt(-1.0).is_nan());
+    assert_eq!(f64::INFINITY, soft_sqrt(f64::INFINITY));
+}
+
+/// Kahan compensated sum of input values, carrying the
+/// low-order bits lost by each addition into the next.
+fn compensated_sum<T: Float>(nums: &[T]) -> T {
+    compensated_sum_iter(nums.iter().copied())
+}
+
+/// Kahan compensated sum of the values of `iter`, as for
+/// `compensated_sum`.
+fn compensated_sum_iter<T: Float, I: Iterator<Item = T>>(iter: I) -> T {
+    let mut sum = T::zero();
+    let mut compensation = T::zero();
+    for num in iter {
+        let y = num - compensation;
+        let t = sum + y;
+        compensation = (t - sum) - y;
+        sum = t;
+    }
+    sum
+}
+
+/// Sum of input values using Kahan compensated summation,
+/// which loses far less precision than naive accumulation
+/// when values differ widely in magnitude. The sum of an
+/// empty list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(0.0, kahan_sum(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(6.0, kahan_sum(&[1.0, 2.0, 3.0]));
+/// ```
+pub fn kahan_sum(nums: &[f64]) -> f64 {
+    compensated_sum(nums)
+}
+
+#[test]
+fn test_kahan_sum_magnitudes() {
+    let mut nums = vec![1e16];
+    nums.resize(1_000_001, 1.0);
+    let exact = 1e16 + 1e6;
+    let naive = nums.iter().fold(0.0, |acc, num| acc + num);
+    let kahan = kahan_sum(&nums);
+    assert!((kahan - exact).abs() < (naive - exact).abs());
+    assert_eq!(exact, kahan);
+}
+
 /// Arithmetic mean of input values. The mean of an empty
-/// list is 0.0.
+/// list is undefined.
 ///
 /// # Examples:
 ///
 /// ```
 /// # use stats::*;
-/// assert_eq!(Some(0.0), mean(&[]));
+/// assert_eq!(None, mean::<f64>(&[]));
 /// ```
 /// ```
 /// # use stats::*;
 /// assert_eq!(Some(0.0), mean(&[-1.0, 1.0]));
 /// ```
-pub fn mean(nums: &[f64]) -> Option<f64> {
-    let count = nums.len() as f64;
-    let mut arithmetic = 0.0;
-    let mut sum = 0.0;
-    if count != 0.0 {
-        for num in &nums[..] {
-            sum += num;
-        }
-        arithmetic = sum / count;
+pub fn mean<T: Float>(nums: &[T]) -> Option<T> {
+    if nums.is_empty() {
+        return None;
     }
-    Some(arithmetic)
+    let count = T::from_usize(nums.len());
+    Some(compensated_sum(nums) / count)
 }
 
 #[test]
@@ -51,40 +245,123 @@ fn test_mean_two() {
     assert_eq!(Some(1.0), mean(&[-1.0, 3.0]));
 }
 
+#[test]
+fn test_mean_f32() {
+    assert_eq!(Some(100.0f32), mean(&[75.5f32, 100.5, 95.5, 265.5, -37.0]));
+}
+
+/// Sum of squared deviations of input values from their
+/// mean, computed in a single pass with Welford's
+/// algorithm. Shared by the variance computations.
+fn sum_sq_dev<T: Float>(nums: &[T]) -> Option<T> {
+    if nums.is_empty() {
+        return None;
+    }
+    let mut count = T::zero();
+    let mut xbar = T::zero();
+    let mut sum = T::zero();
+    for &val in nums {
+        count += T::one();
+        let delta = val - xbar;
+        xbar += delta / count;
+        sum += delta * (val - xbar);
+    }
+    Some(sum)
+}
+
+/// Population variance of input values. The variance of an
+/// empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, variance::<f64>(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), variance(&[-1.0, 3.0]));
+/// ```
+pub fn variance<T: Float>(nums: &[T]) -> Option<T> {
+    let count = T::from_usize(nums.len());
+    sum_sq_dev(nums).map(|sum| sum / count)
+}
+
+#[test]
+fn test_variance_single() {
+    assert_eq!(Some(0.0), variance(&[25.0]));
+}
+
+#[test]
+fn test_variance_two() {
+    assert_eq!(Some(4.0), variance(&[-1.0, 3.0]));
+}
+
+/// Sample variance of input values, using Bessel's
+/// correction (dividing by `n-1`). The sample variance of a
+/// list with fewer than two elements is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_variance(&[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(8.0), sample_variance(&[-1.0, 3.0]));
+/// ```
+pub fn sample_variance(nums: &[f64]) -> Option<f64> {
+    if nums.len() < 2 {
+        return None;
+    }
+    let count = nums.len() as f64;
+    sum_sq_dev(nums).map(|sum| sum / (count - 1.0))
+}
+
+#[test]
+fn test_sample_variance_empty() {
+    assert_eq!(None, sample_variance(&[]));
+}
+
+#[test]
+fn test_sample_variance_single() {
+    assert_eq!(None, sample_variance(&[25.0]));
+}
+
+#[test]
+fn test_sample_variance_100() {
+    assert_eq!(
+        Some(11695.0),
+        sample_variance(&[75.5, 100.5, 95.5, 265.5, -37.0])
+    );
+}
+
 /// Population standard deviation of input values. The
-/// standard deviation of an empty list is undefined.
+/// standard deviation of an empty list is undefined. See
+/// `sample_stddev` for the sample standard deviation.
 ///
 /// # Examples:
 ///
 /// ```
 /// # use stats::*;
-/// assert_eq!(None, stddev(&[]));
+/// assert_eq!(None, stddev::<f64>(&[]));
 /// ```
 /// ```
 /// # use stats::*;
 /// assert_eq!(Some(0.0), stddev(&[1.0, 1.0]));
 /// ```
-pub fn stddev(nums: &[f64]) -> Option<f64> {
-    let mut sigma = 0.0;
-    let mut sqnums = Vec::new();
-    let xbar = mean(nums).unwrap() as f64; //no error since mean will be Some
-    if !nums.is_empty() {
-        for val in &nums[..] {
-            let temp = (val - xbar).powf(2.0);
-            sqnums.push(temp);
-            sigma = mean(&sqnums[..]).unwrap().sqrt() as f64;
-        }
-        Some(sigma)
-    } else {
-        None
-    }
+pub fn stddev<T: Float>(nums: &[T]) -> Option<T> {
+    variance(nums).map(T::sqrt)
 }
 
 #[test]
 fn test_stdev_97() {
     assert_eq!(
         97.0,
-        stddev(&[75.5, 100.5, 95.5, 265.5, -37.0]).unwrap().round()
+        stddev(&[75.5f64, 100.5, 95.5, 265.5, -37.0])
+            .unwrap()
+            .round()
     );
 }
 
@@ -98,50 +375,244 @@ fn test_stdev_two() {
     assert_eq!(Some(2.0), stddev(&[-1.0, 3.0]));
 }
 
+#[test]
+fn test_stdev_f32() {
+    assert_eq!(Some(2.0f32), stddev(&[-1.0f32, 3.0]));
+}
+
+#[test]
+fn test_stdev_two_pass() {
+    let nums = [75.5f64, 100.5, 95.5, 265.5, -37.0];
+    let xbar = mean(&nums).unwrap();
+    let mut sum = 0.0;
+    for val in &nums {
+        sum += (val - xbar).powf(2.0);
+    }
+    let expected = (sum / nums.len() as f64).sqrt();
+    assert!((expected - stddev(&nums).unwrap()).abs() < 1e-9);
+    assert_eq!(97.0, expected.round());
+}
+
+#[test]
+fn test_stdev_large() {
+    let n = 100_000;
+    let nums: Vec<f64> = (0..n).map(f64::from).collect();
+    let expected = ((f64::from(n).powf(2.0) - 1.0) / 12.0).sqrt();
+    assert!((expected - stddev(&nums).unwrap()).abs() < 1e-6);
+}
+
+/// Sample standard deviation of input values, using
+/// Bessel's correction (dividing by `n-1`). The sample
+/// standard deviation of a list with fewer than two
+/// elements is undefined. See `stddev` for the population
+/// standard deviation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_stddev(&[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), sample_stddev(&[1.0, 1.0]));
+/// ```
+pub fn sample_stddev(nums: &[f64]) -> Option<f64> {
+    sample_variance(nums).map(f64::sqrt)
+}
+
+#[test]
+fn test_sample_stddev_empty() {
+    assert_eq!(None, sample_stddev(&[]));
+}
+
+#[test]
+fn test_sample_stddev_two() {
+    assert_eq!(Some(8.0f64.sqrt()), sample_stddev(&[-1.0, 3.0]));
+}
+
+/// Sorted copy of input values. `NaN` has no place in the
+/// ordering, so a list containing `NaN` has no sorted copy.
+#[cfg(feature = "alloc")]
+fn sorted(nums: &[f64]) -> Option<Vec<f64>> {
+    if nums.iter().any(|num| num.is_nan()) {
+        return None;
+    }
+    let mut nums = nums.to_vec();
+    // https://users.rust-lang.org/t/how-to-sort-a-vec-of-floats/2838/2
+    nums.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Some(nums)
+}
+
+/// Value that would be at index `n` of `nums` once sorted,
+/// found by quickselect in expected linear time. Reorders
+/// `nums` so that no value before index `n` is greater and
+/// no value after it is less. `nums` must be non-empty and
+/// must not contain `NaN`.
+#[cfg(feature = "alloc")]
+fn select_nth<T: Float>(nums: &mut [T], n: usize) -> T {
+    let mut lo = 0;
+    let mut hi = nums.len();
+    loop {
+        // Median-of-three pivot guards against sorted input.
+        let (a, b, c) = (nums[lo], nums[lo + (hi - lo) / 2], nums[hi - 1]);
+        let pivot = a.min(b).max(a.max(b).min(c));
+        // Three-way partition of nums[lo..hi] into values
+        // below, equal to and above the pivot.
+        let mut lt = lo;
+        let mut i = lo;
+        let mut gt = hi;
+        while i < gt {
+            if nums[i] < pivot {
+                nums.swap(lt, i);
+                lt += 1;
+                i += 1;
+            } else if nums[i] > pivot {
+                gt -= 1;
+                nums.swap(i, gt);
+            } else {
+                i += 1;
+            }
+        }
+        if n < lt {
+            hi = lt;
+        } else if n >= gt {
+            lo = gt;
+        } else {
+            return pivot;
+        }
+    }
+}
+
 /// Median value of input values, taking the value closer
 /// to the beginning to break ties. The median
-/// of an empty list is undefined.
+/// of an empty list is undefined, as is the median of a
+/// list containing `NaN`.
 ///
 /// # Examples:
 ///
 /// ```
 /// # use stats::*;
-/// assert_eq!(None, median(&[]));
+/// assert_eq!(None, median::<f64>(&[]));
 /// ```
 /// ```
 /// # use stats::*;
 /// assert_eq!(Some(0.0), median(&[0.0, 0.5, -1.0, 1.0]));
 /// ```
-pub fn median(nums: &[f64]) -> Option<f64> {
-    // Make a sorted copy of the input floats.
-    let mut nums = nums.to_owned();
-    // https://users.rust-lang.org/t/how-to-sort-a-vec-of-floats/2838/2
-    nums.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let mut index = nums.len();
-    if index != 0 {
-        index = (index - 1) / 2;
-        let med = nums[index];
-        Some(med)
-    } else {
-        None
+#[cfg(feature = "alloc")]
+pub fn median<T: Float>(nums: &[T]) -> Option<T> {
+    if nums.is_empty() || nums.iter().any(|num| num.is_nan()) {
+        return None;
     }
+    let mut nums = nums.to_vec();
+    let index = (nums.len() - 1) / 2;
+    Some(select_nth(&mut nums, index))
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_median_95() {
     assert_eq!(Some(95.5), median(&[75.5, 100.5, 95.5, 265.5, -37.0]));
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_median_single() {
     assert_eq!(Some(25.0), median(&[25.0]));
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_median_two() {
     assert_eq!(Some(-1.0), median(&[-1.0, 3.0]));
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_f32() {
+    assert_eq!(Some(95.5f32), median(&[75.5f32, 100.5, 95.5, 265.5, -37.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_nan() {
+    assert_eq!(None, median(&[1.0, f64::NAN, 3.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_all_nan() {
+    assert_eq!(None, median(&[f64::NAN]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_large_shuffled() {
+    for &n in &[10_007, 10_008] {
+        // Stride through the values to shuffle them, with
+        // plenty of duplicates.
+        let nums: Vec<f64> = (0..n).map(|i| ((i * 7919) % n % 1000) as f64).collect();
+        let reference = sorted(&nums).unwrap()[(n - 1) / 2];
+        assert_eq!(Some(reference), median(&nums));
+    }
+}
+
+/// Median value of input values, averaging the two middle
+/// values of an even-length list. The median of an empty
+/// list is undefined, as is the median of a list
+/// containing `NaN`. See `median` for a median that never
+/// averages.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median_averaged(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.25), median_averaged(&[0.0, 0.5, -1.0, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn median_averaged(nums: &[f64]) -> Option<f64> {
+    if nums.is_empty() || nums.iter().any(|num| num.is_nan()) {
+        return None;
+    }
+    let mut nums = nums.to_vec();
+    let half = nums.len() / 2;
+    let upper = select_nth(&mut nums, half);
+    if nums.len() % 2 == 1 {
+        return Some(upper);
+    }
+    // The lower middle value is the largest value below half.
+    let lower = nums[..half]
+        .iter()
+        .fold(f64::NEG_INFINITY, |acc, &num| acc.max(num));
+    Some((lower + upper) / 2.0)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_averaged_even() {
+    assert_eq!(Some(2.5), median_averaged(&[4.0, 1.0, 3.0, 2.0]));
+    assert_eq!(Some(2.0), median(&[4.0, 1.0, 3.0, 2.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_averaged_odd() {
+    assert_eq!(
+        Some(95.5),
+        median_averaged(&[75.5, 100.5, 95.5, 265.5, -37.0])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_averaged_nan() {
+    assert_eq!(None, median_averaged(&[1.0, f64::NAN]));
+}
+
 /// L2 norm (Euclidean norm) of input values. The L2
 /// norm of an empty list is 0.0.
 ///
@@ -149,29 +620,52 @@ fn test_median_two() {
 ///
 /// ```
 /// # use stats::*;
-/// assert_eq!(Some(0.0), l2(&[]));
+/// assert_eq!(Some(0.0), l2::<f64>(&[]));
 /// ```
 /// ```
 /// # use stats::*;
 /// assert_eq!(Some(5.0), l2(&[-3.0, 4.0]));
 /// ```
-pub fn l2(nums: &[f64]) -> Option<f64> {
-    let mut norm = 0.0;
-    let mut sum = 0.0;
-    if !nums.is_empty() {
-        for val in &nums[..] {
-            sum += val.powf(2.0);
-        }
-        norm = sum.sqrt();
+pub fn l2<T: Float>(nums: &[T]) -> Option<T> {
+    Some(scaled_norm(nums.iter().copied()))
+}
+
+/// Euclidean norm of `iter`, summing squares relative to the
+/// largest magnitude so far as BLAS `nrm2` does, so that
+/// large values do not overflow nor small ones underflow.
+fn scaled_norm<T: Float, I: Iterator<Item = T>>(iter: I) -> T {
+    let (scale, ssq) = iter.fold((T::zero(), T::one()), |acc, val| {
+        combine_scaled(acc, (val.abs(), T::one()))
+    });
+    scale * ssq.sqrt()
+}
+
+/// Combine two partial sums of squares, each held as
+/// `(scale, ssq)` standing for `scale^2 * ssq`, rescaling
+/// to the larger scale. A zero scale stands for no values.
+fn combine_scaled<T: Float>(a: (T, T), b: (T, T)) -> (T, T) {
+    if b.0 == T::zero() {
+        return a;
+    }
+    if a.0 == T::zero() {
+        return b;
+    }
+    if a.0 == b.0 {
+        (a.0, a.1 + b.1)
+    } else if a.0 < b.0 {
+        let ratio = a.0 / b.0;
+        (b.0, b.1 + a.1 * ratio * ratio)
+    } else {
+        let ratio = b.0 / a.0;
+        (a.0, a.1 + b.1 * ratio * ratio)
     }
-    Some(norm)
 }
 
 #[test]
 fn test_l2_311() {
     assert_eq!(
         311.0,
-        l2(&[75.5, 100.5, 95.5, 265.5, -37.0]).unwrap().round()
+        l2(&[75.5f64, 100.5, 95.5, 265.5, -37.0]).unwrap().round()
     );
 }
 
@@ -182,5 +676,5526 @@ fn test_l2_single() {
 
 #[test]
 fn test_l2_two() {
-    assert_eq!(3.0, l2(&[-1.0, 3.0]).unwrap().round());
+    assert_eq!(3.0, l2(&[-1.0f64, 3.0]).unwrap().round());
+}
+
+#[test]
+fn test_l2_f32() {
+    assert_eq!(Some(5.0f32), l2(&[-3.0f32, 4.0]));
+}
+
+#[test]
+fn test_l2_no_overflow() {
+    let norm = l2(&[1e200, 1e200]).unwrap();
+    assert!((norm - 2f64.sqrt() * 1e200).abs() < 1e186);
+}
+
+#[test]
+fn test_l2_no_underflow() {
+    let norm = l2(&[3e-200, -4e-200]).unwrap();
+    assert!((norm - 5e-200).abs() < 1e-214);
+}
+
+#[test]
+fn test_l2_infinite_nan() {
+    assert_eq!(Some(f64::INFINITY), l2(&[1.0, f64::NEG_INFINITY]));
+    assert!(l2(&[1.0, f64::NAN]).unwrap().is_nan());
+}
+
+/// L1 norm (Manhattan norm) of input values. The L1 norm
+/// of an empty list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), l1(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), l1(&[-3.0, 4.0]));
+/// ```
+pub fn l1(nums: &[f64]) -> Option<f64> {
+    let mut sum = 0.0;
+    for val in nums {
+        sum += val.abs();
+    }
+    Some(sum)
+}
+
+#[test]
+fn test_l1_single() {
+    assert_eq!(Some(25.0), l1(&[-25.0]));
+}
+
+#[test]
+fn test_l1_two() {
+    assert_eq!(Some(7.0), l1(&[-3.0, 4.0]));
+}
+
+/// Lp norm of input values, `(sum |x|^p)^(1/p)`. An
+/// infinite `p` gives the maximum absolute value (Chebyshev
+/// norm). The Lp norm of an empty list is 0.0, and it is
+/// undefined for `p <= 0.0`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, lp_norm(&[1.0], 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), lp_norm(&[-3.0, 4.0], f64::INFINITY));
+/// ```
+#[cfg(feature = "std")]
+pub fn lp_norm(nums: &[f64], p: f64) -> Option<f64> {
+    if p.is_nan() || p <= 0.0 {
+        return None;
+    }
+    if p == f64::INFINITY {
+        let mut norm = 0.0;
+        for val in nums {
+            norm = val.abs().max(norm);
+        }
+        return Some(norm);
+    }
+    let mut sum = 0.0;
+    for val in nums {
+        sum += val.abs().powf(p);
+    }
+    Some(sum.powf(1.0 / p))
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lp_norm_l2() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    let diff = lp_norm(&nums, 2.0).unwrap() - l2(&nums).unwrap();
+    assert!(diff.abs() < 1e-9);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lp_norm_infinity() {
+    assert_eq!(
+        Some(265.5),
+        lp_norm(&[75.5, 100.5, 95.5, -265.5, -37.0], f64::INFINITY)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_lp_norm_empty() {
+    assert_eq!(Some(0.0), lp_norm(&[], 3.0));
+    assert_eq!(Some(0.0), lp_norm(&[], f64::INFINITY));
+}
+
+/// Root mean square of input values, `sqrt(mean(x^2))`.
+/// The root mean square of an empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, root_mean_square(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), root_mean_square(&[-2.0, 2.0]));
+/// ```
+pub fn root_mean_square(nums: &[f64]) -> Option<f64> {
+    if nums.is_empty() {
+        return None;
+    }
+    let count = nums.len() as f64;
+    l2(nums).map(|norm| norm / count.sqrt())
+}
+
+#[test]
+fn test_root_mean_square_two() {
+    let rms = root_mean_square(&[3.0, 4.0]).unwrap();
+    assert!((rms - 12.5f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn test_root_mean_square_single() {
+    assert_eq!(Some(25.0), root_mean_square(&[-25.0]));
+}
+
+/// Most frequent value among input values paired with its
+/// count, grouping values within `epsilon` of the smallest
+/// value in their group. Ties are broken by choosing the
+/// smallest value.
+#[cfg(feature = "alloc")]
+fn mode_count(nums: &[f64], epsilon: f64) -> Option<(f64, usize)> {
+    if nums.is_empty() || epsilon.is_nan() || epsilon < 0.0 {
+        return None;
+    }
+    let nums = sorted(nums)?;
+    let mut best = (nums[0], 0);
+    let mut start = 0;
+    while start < nums.len() {
+        let mut end = start + 1;
+        // Equal infinities differ by NaN, so compare them
+        // first.
+        while end < nums.len() && (nums[end] == nums[start] || nums[end] - nums[start] <= epsilon) {
+            end += 1;
+        }
+        if end - start > best.1 {
+            best = (nums[start], end - start);
+        }
+        start = end;
+    }
+    Some(best)
+}
+
+/// Mode (most frequent value) of input values, taking the
+/// smallest value to break ties. The mode of an empty list
+/// is undefined, as is the mode of a list containing `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, mode(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), mode(&[1.0, 2.0, 2.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mode(nums: &[f64]) -> Option<f64> {
+    mode_within(nums, 0.0)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_single_mode() {
+    assert_eq!(Some(3.0), mode(&[3.0, 1.0, 3.0, 2.0, 3.0, 1.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_tie() {
+    assert_eq!(Some(1.0), mode(&[3.0, 1.0, 3.0, 1.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_multimodal() {
+    assert_eq!(Some(-2.0), mode(&[5.0, -2.0, 7.0, 5.0, -2.0, 7.0, 0.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_infinite() {
+    let nums = [1.0, f64::INFINITY, f64::INFINITY];
+    assert_eq!(Some(f64::INFINITY), mode(&nums));
+    assert_eq!(
+        Some(f64::NEG_INFINITY),
+        mode_within(&[f64::NEG_INFINITY; 2], 0.5)
+    );
+}
+
+/// Mode of input values, treating values within `epsilon`
+/// of the smallest value in a group as equal. The group is
+/// represented by its smallest value, and ties are broken
+/// by choosing the smallest group. The mode is undefined
+/// for an empty list, a list containing `NaN`, or a
+/// negative `epsilon`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), mode_within(&[1.0, 2.0, 2.05, 3.0], 0.1));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mode_within(nums: &[f64], epsilon: f64) -> Option<f64> {
+    mode_count(nums, epsilon).map(|(value, _)| value)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_within_groups() {
+    assert_eq!(Some(0.99), mode_within(&[0.99, 1.0, 1.01, 2.0, 2.0], 0.05));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_within_negative_epsilon() {
+    assert_eq!(None, mode_within(&[1.0, 1.0], -1.0));
+}
+
+/// Every mode (most frequent value) of input values,
+/// sorted ascending, for when several values tie. With no
+/// tie this is just `mode`. An empty list, or a list
+/// containing `NaN`, has no modes.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(mode_all(&[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![1.0, 3.0], mode_all(&[3.0, 1.0, 2.0, 3.0, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mode_all(nums: &[f64]) -> Vec<f64> {
+    let nums = match sorted(nums) {
+        Some(nums) => nums,
+        None => return Vec::new(),
+    };
+    let mut modes = Vec::new();
+    let mut best = 0;
+    let mut start = 0;
+    while start < nums.len() {
+        let mut end = start + 1;
+        while end < nums.len() && nums[end] == nums[start] {
+            end += 1;
+        }
+        if end - start > best {
+            best = end - start;
+            modes.clear();
+        }
+        if end - start == best {
+            modes.push(nums[start]);
+        }
+        start = end;
+    }
+    modes
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_all_tie() {
+    assert_eq!(vec![-2.0, 5.0], mode_all(&[5.0, -2.0, 7.0, 5.0, -2.0, 0.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_all_single() {
+    let nums = [3.0, 1.0, 3.0, 2.0, 3.0, 1.0];
+    assert_eq!(vec![mode(&nums).unwrap()], mode_all(&nums));
+    assert_eq!(vec![1.0, 2.0], mode_all(&[2.0, 1.0]));
+    assert!(mode_all(&[1.0, f64::NAN]).is_empty());
+}
+
+/// Mode of input values, as for `mode`, paired with the
+/// number of times it occurs. The mode of an empty list is
+/// undefined, as is the mode of a list containing `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, mode_with_count(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some((2.0, 2)), mode_with_count(&[1.0, 2.0, 2.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mode_with_count(nums: &[f64]) -> Option<(f64, usize)> {
+    mode_count(nums, 0.0)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mode_with_count_tie() {
+    assert_eq!(Some((1.0, 2)), mode_with_count(&[3.0, 1.0, 3.0, 1.0]));
+    assert_eq!(Some((4.0, 1)), mode_with_count(&[4.0]));
+    assert_eq!(None, mode_with_count(&[1.0, f64::NAN]));
+}
+
+/// Minimum of input values, skipping `NaN`. The minimum of
+/// an empty list, or of a list that is all `NaN`, is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, min(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-1.0), min(&[0.0, f64::NAN, -1.0]));
+/// ```
+pub fn min(nums: &[f64]) -> Option<f64> {
+    // f64::min ignores a NaN argument, so a NaN result means
+    // no number was seen.
+    let result = nums.iter().fold(f64::NAN, |acc, &num| acc.min(num));
+    if result.is_nan() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+#[test]
+fn test_min_mixed_sign() {
+    assert_eq!(Some(-37.0), min(&[75.5, 100.5, 95.5, 265.5, -37.0]));
+}
+
+#[test]
+fn test_min_all_nan() {
+    assert_eq!(None, min(&[f64::NAN, f64::NAN]));
+}
+
+/// Maximum of input values, skipping `NaN`. The maximum of
+/// an empty list, or of a list that is all `NaN`, is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, max(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), max(&[0.0, f64::NAN, 1.0]));
+/// ```
+pub fn max(nums: &[f64]) -> Option<f64> {
+    let result = nums.iter().fold(f64::NAN, |acc, &num| acc.max(num));
+    if result.is_nan() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+#[test]
+fn test_max_mixed_sign() {
+    assert_eq!(Some(265.5), max(&[75.5, 100.5, 95.5, 265.5, -37.0]));
+}
+
+#[test]
+fn test_max_all_nan() {
+    assert_eq!(None, max(&[f64::NAN, f64::NAN]));
+}
+
+/// Range (maximum minus minimum) of input values, skipping
+/// `NaN`. The range of an empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, range(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), range(&[-1.0, 0.0, 1.0]));
+/// ```
+pub fn range(nums: &[f64]) -> Option<f64> {
+    Some(max(nums)? - min(nums)?)
+}
+
+#[test]
+fn test_range_single() {
+    assert_eq!(Some(0.0), range(&[25.0]));
+}
+
+#[test]
+fn test_range_negative() {
+    assert_eq!(Some(302.5), range(&[-37.0, 265.5]));
+}
+
+/// Linearly interpolated quantile of already-sorted values,
+/// as for `quantile`.
+#[cfg(feature = "alloc")]
+fn sorted_quantile(nums: &[f64], q: f64) -> Option<f64> {
+    if nums.is_empty() || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    let index = q * (nums.len() - 1) as f64;
+    // The index is non-negative, so truncation is floor.
+    let lower = index as usize;
+    let frac = index - lower as f64;
+    // At a whole index no interpolation is needed, and
+    // attempting it would turn an infinite value into NaN.
+    if frac == 0.0 {
+        return Some(nums[lower]);
+    }
+    Some(nums[lower] + (nums[lower + 1] - nums[lower]) * frac)
+}
+
+/// Quantile `q` of input values, for `q` in `[0.0, 1.0]`.
+/// The fractional index `q * (n-1)` into the sorted values
+/// is linearly interpolated between the two surrounding
+/// values (NumPy's default). The quantile is undefined for
+/// an empty list, a list containing `NaN`, or a `q` outside
+/// `[0.0, 1.0]`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, quantile(&[], 0.5));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.5), quantile(&[4.0, 1.0, 3.0, 2.0], 0.5));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn quantile(nums: &[f64], q: f64) -> Option<f64> {
+    sorted_quantile(&sorted(nums)?, q)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_quantile_interpolated() {
+    assert_eq!(Some(2.5), quantile(&[1.0, 2.0, 3.0, 4.0], 0.5));
+    assert_eq!(Some(1.75), quantile(&[1.0, 2.0, 3.0, 4.0], 0.25));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_quantile_endpoints() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    assert_eq!(min(&nums), quantile(&nums, 0.0));
+    assert_eq!(max(&nums), quantile(&nums, 1.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_quantile_out_of_range() {
+    assert_eq!(None, quantile(&[1.0, 2.0], -0.1));
+    assert_eq!(None, quantile(&[1.0, 2.0], 1.1));
+    assert_eq!(None, quantile(&[1.0, 2.0], f64::NAN));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_quantile_infinite() {
+    let nums = [1.0, f64::INFINITY, f64::NEG_INFINITY];
+    assert_eq!(Some(f64::INFINITY), quantile(&nums, 1.0));
+    assert_eq!(Some(f64::NEG_INFINITY), quantile(&nums, 0.0));
+    assert_eq!(Some(1.0), quantile(&nums, 0.5));
+}
+
+/// Interquartile range (75th minus 25th percentile) of
+/// input values, interpolated as for `quantile`. The
+/// interquartile range is undefined for an empty list or a
+/// list containing `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, iqr(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.5), iqr(&[1.0, 2.0, 3.0, 4.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn iqr(nums: &[f64]) -> Option<f64> {
+    let nums = sorted(nums)?;
+    Some(sorted_quantile(&nums, 0.75)? - sorted_quantile(&nums, 0.25)?)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iqr_nine() {
+    assert_eq!(
+        Some(4.0),
+        iqr(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iqr_two() {
+    assert_eq!(Some(1.0), iqr(&[3.0, 1.0]));
+}
+
+/// Standardized `k`th central moment of input values,
+/// undefined when the standard deviation is zero.
+#[cfg(feature = "std")]
+fn standardized_moment(nums: &[f64], k: f64) -> Option<f64> {
+    let xbar = mean(nums)?;
+    let sigma = stddev(nums)?;
+    if sigma == 0.0 {
+        return None;
+    }
+    let mut sum = 0.0;
+    for val in nums {
+        sum += ((val - xbar) / sigma).powf(k);
+    }
+    Some(sum / nums.len() as f64)
+}
+
+/// Skewness (standardized third moment) of input values.
+/// The skewness is undefined for an empty list or a list
+/// with zero standard deviation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, skewness(&[1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert!(skewness(&[1.0, 1.0, 1.0, 2.0, 10.0]).unwrap() > 0.0);
+/// ```
+#[cfg(feature = "std")]
+pub fn skewness(nums: &[f64]) -> Option<f64> {
+    standardized_moment(nums, 3.0)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_skewness_symmetric() {
+    assert!(skewness(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap().abs() < 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_skewness_right_skewed() {
+    assert!(skewness(&[1.0, 1.0, 1.0, 2.0, 10.0]).unwrap() > 1.0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_skewness_empty() {
+    assert_eq!(None, skewness(&[]));
+}
+
+/// Excess kurtosis (standardized fourth moment minus 3) of
+/// input values, so that a normal distribution has
+/// kurtosis near 0. The kurtosis is undefined for an empty
+/// list or a list with zero standard deviation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, kurtosis(&[1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-2.0), kurtosis(&[-1.0, 1.0]));
+/// ```
+#[cfg(feature = "std")]
+pub fn kurtosis(nums: &[f64]) -> Option<f64> {
+    standardized_moment(nums, 4.0).map(|moment| moment - 3.0)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_kurtosis_uniform() {
+    // The five-point discrete uniform has excess kurtosis -1.3.
+    let excess = kurtosis(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    assert!((excess + 1.3).abs() < 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_kurtosis_heavy_tail() {
+    assert!(kurtosis(&[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 10.0]).unwrap() > 0.0);
+}
+
+/// Sum of products of paired deviations of `xs` and `ys`
+/// from their means. Shared by the covariance computations.
+fn sum_co_dev(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    let xbar = mean(xs)?;
+    let ybar = mean(ys)?;
+    let mut sum = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        sum += (x - xbar) * (y - ybar);
+    }
+    Some(sum)
+}
+
+/// Population covariance of paired input values. The
+/// covariance is undefined for empty lists or lists of
+/// differing lengths.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, covariance(&[1.0, 2.0], &[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), covariance(&[-1.0, 1.0], &[-2.0, 2.0]));
+/// ```
+pub fn covariance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let count = xs.len() as f64;
+    sum_co_dev(xs, ys).map(|sum| sum / count)
+}
+
+#[test]
+fn test_covariance_correlated() {
+    let xs = [1.0, 2.0, 3.0, 4.0];
+    assert_eq!(variance(&xs), covariance(&xs, &xs));
+}
+
+#[test]
+fn test_covariance_anticorrelated() {
+    assert_eq!(
+        Some(-12.5),
+        covariance(&[1.0, 2.0, 3.0, 4.0], &[-10.0, -20.0, -30.0, -40.0])
+    );
+}
+
+#[test]
+fn test_covariance_empty() {
+    assert_eq!(None, covariance(&[], &[]));
+}
+
+/// Sample covariance of paired input values, dividing by
+/// `n-1`. The sample covariance is undefined for lists of
+/// differing lengths or with fewer than two elements.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_covariance(&[1.0], &[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), sample_covariance(&[-1.0, 1.0], &[-2.0, 2.0]));
+/// ```
+pub fn sample_covariance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() < 2 {
+        return None;
+    }
+    let count = xs.len() as f64;
+    sum_co_dev(xs, ys).map(|sum| sum / (count - 1.0))
+}
+
+#[test]
+fn test_sample_covariance_correlated() {
+    let xs = [1.0, 2.0, 3.0, 4.0];
+    assert_eq!(sample_variance(&xs), sample_covariance(&xs, &xs));
+}
+
+#[test]
+fn test_sample_covariance_anticorrelated() {
+    let cov = sample_covariance(&[1.0, 2.0, 3.0, 4.0], &[-10.0, -20.0, -30.0, -40.0]);
+    assert!((cov.unwrap() + 50.0 / 3.0).abs() < 1e-12);
+}
+
+/// Population covariance matrix of `columns`, whose entry
+/// `(i, j)` is the covariance of columns `i` and `j`. The
+/// matrix is undefined for no columns, empty columns, or
+/// columns of differing lengths.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, covariance_matrix(&[vec![1.0, 2.0], vec![1.0]]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let columns = [vec![-1.0, 1.0], vec![2.0, -2.0]];
+/// assert_eq!(
+///     Some(vec![vec![1.0, -2.0], vec![-2.0, 4.0]]),
+///     covariance_matrix(&columns)
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn covariance_matrix(columns: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    if columns.is_empty() {
+        return None;
+    }
+    let n = columns.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i..n {
+            let cov = covariance(&columns[i], &columns[j])?;
+            matrix[i][j] = cov;
+            matrix[j][i] = cov;
+        }
+    }
+    Some(matrix)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_covariance_matrix_two() {
+    let xs = vec![1.0, 2.0, 3.0, 4.0];
+    let ys = vec![-10.0, -20.0, -30.0, -40.0];
+    let matrix = covariance_matrix(&[xs.clone(), ys.clone()]).unwrap();
+    assert_eq!(variance(&xs), Some(matrix[0][0]));
+    assert_eq!(variance(&ys), Some(matrix[1][1]));
+    assert_eq!(covariance(&xs, &ys), Some(matrix[0][1]));
+    assert_eq!(matrix[0][1], matrix[1][0]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_covariance_matrix_invalid() {
+    assert_eq!(None, covariance_matrix(&[]));
+    assert_eq!(None, covariance_matrix(&[vec![], vec![]]));
+    assert_eq!(None, covariance_matrix(&[vec![1.0, 2.0], vec![1.0]]));
+}
+
+/// Pearson correlation coefficient of paired input values,
+/// in `[-1.0, 1.0]`. The correlation is undefined for empty
+/// lists, lists of differing lengths, or when either list
+/// has zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, pearson(&[1.0, 2.0], &[3.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-1.0), pearson(&[1.0, 2.0], &[2.0, 1.0]));
+/// ```
+pub fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let cov = covariance(xs, ys)?;
+    let denom = (variance(xs)? * variance(ys)?).sqrt();
+    if denom == 0.0 {
+        return None;
+    }
+    // Rounding can push a perfect correlation just past 1.
+    Some((cov / denom).clamp(-1.0, 1.0))
+}
+
+#[test]
+fn test_pearson_scaled() {
+    let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let ys: Vec<f64> = xs.iter().map(|x| 2.0 * x).collect();
+    assert_eq!(Some(1.0), pearson(&xs, &ys));
+}
+
+#[test]
+fn test_pearson_negated() {
+    let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let ys: Vec<f64> = xs.iter().map(|x| -x).collect();
+    assert_eq!(Some(-1.0), pearson(&xs, &ys));
+}
+
+#[test]
+fn test_pearson_mismatched() {
+    assert_eq!(None, pearson(&[1.0, 2.0, 3.0], &[1.0, 2.0]));
+    assert_eq!(None, pearson(&[], &[]));
+}
+
+/// Pearson correlation matrix of `columns`, whose entry
+/// `(i, j)` is the correlation of columns `i` and `j`, with
+/// ones on the diagonal. The matrix is undefined for no
+/// columns, empty columns, columns of differing lengths, or
+/// when any column has zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, correlation_matrix(&[vec![1.0, 2.0], vec![3.0, 3.0]]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let columns = [vec![-1.0, 1.0], vec![2.0, -2.0]];
+/// assert_eq!(
+///     Some(vec![vec![1.0, -1.0], vec![-1.0, 1.0]]),
+///     correlation_matrix(&columns)
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn correlation_matrix(columns: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let mut matrix = covariance_matrix(columns)?;
+    let scales: Vec<f64> = (0..matrix.len()).map(|i| matrix[i][i].sqrt()).collect();
+    if scales.contains(&0.0) {
+        return None;
+    }
+    for (i, row) in matrix.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            // Rounding can push a perfect correlation just past 1.
+            *entry = if i == j {
+                1.0
+            } else {
+                (*entry / (scales[i] * scales[j])).clamp(-1.0, 1.0)
+            };
+        }
+    }
+    Some(matrix)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_correlation_matrix_three() {
+    let xs = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    let ys = vec![2.0, 1.0, 4.0, 3.0, 7.0];
+    let zs = vec![-3.0, 0.5, -1.0, 2.0, 0.0];
+    let matrix = correlation_matrix(&[xs.clone(), ys.clone(), zs]).unwrap();
+    for (i, row) in matrix.iter().enumerate() {
+        assert_eq!(1.0, row[i]);
+        for (j, &entry) in row.iter().enumerate() {
+            assert_eq!(entry, matrix[j][i]);
+        }
+    }
+    assert!((matrix[0][1] - pearson(&xs, &ys).unwrap()).abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_correlation_matrix_invalid() {
+    assert_eq!(None, correlation_matrix(&[]));
+    assert_eq!(None, correlation_matrix(&[vec![1.0, 2.0], vec![1.0]]));
+    assert_eq!(None, correlation_matrix(&[vec![1.0, 2.0], vec![3.0, 3.0]]));
+}
+
+/// Fractional (1-based) ranks of input values, averaging
+/// the ranks of tied values. `NaN` values are left out of
+/// the ranking and get rank `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(rank(&[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![2.0, 1.0, 3.0], rank(&[0.0, -1.5, 8.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn rank(nums: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..nums.len()).filter(|&i| !nums[i].is_nan()).collect();
+    order.sort_by(|&a, &b| nums[a].partial_cmp(&nums[b]).unwrap());
+    let mut ranks = vec![f64::NAN; nums.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && nums[order[end]] == nums[order[start]] {
+            end += 1;
+        }
+        // Positions start..end share the mean of ranks start+1..=end.
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Spearman rank correlation coefficient of paired input
+/// values: the Pearson correlation of their fractional
+/// ranks. The correlation is undefined for empty lists,
+/// lists of differing lengths, lists containing `NaN`, or
+/// when either list is constant.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, spearman(&[], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-1.0), spearman(&[1.0, 2.0, 3.0], &[9.0, 4.0, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn spearman(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.iter().chain(ys).any(|num| num.is_nan()) {
+        return None;
+    }
+    pearson(&rank(xs), &rank(ys))
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_spearman_cubic() {
+    let xs = [-2.0, -1.0, 0.5, 3.0, 4.0, 7.0];
+    let ys: Vec<f64> = xs.iter().map(|x| x * x * x).collect();
+    assert_eq!(Some(1.0), spearman(&xs, &ys));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_spearman_ties() {
+    let xs = [10.0, 20.0, 20.0, 30.0];
+    assert_eq!(
+        pearson(&rank(&xs), &[1.0, 2.0, 3.0, 4.0]),
+        spearman(&xs, &[1.0, 2.0, 3.0, 4.0])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rank_ties() {
+    assert_eq!(vec![1.0, 2.5, 2.5, 4.0], rank(&[10.0, 20.0, 20.0, 30.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rank_nan() {
+    let ranks = rank(&[3.0, f64::NAN, 1.0]);
+    assert_eq!((2.0, 1.0), (ranks[0], ranks[2]));
+    assert!(ranks[1].is_nan());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_spearman_mismatched() {
+    assert_eq!(None, spearman(&[1.0, 2.0], &[1.0]));
+}
+
+/// Ordinary least-squares fit of `ys` against `xs`,
+/// returned as `(slope, intercept)`. The fit is undefined
+/// for lists of differing lengths, fewer than two points,
+/// or when `xs` has zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, linear_regression(&[1.0, 1.0], &[1.0, 2.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some((2.0, 0.0)), linear_regression(&[0.0, 1.0], &[0.0, 2.0]));
+/// ```
+pub fn linear_regression(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+    if xs.len() < 2 {
+        return None;
+    }
+    let cov = covariance(xs, ys)?;
+    let var = variance(xs)?;
+    if var == 0.0 {
+        return None;
+    }
+    let slope = cov / var;
+    let intercept = mean(ys)? - slope * mean(xs)?;
+    Some((slope, intercept))
+}
+
+#[test]
+fn test_linear_regression_line() {
+    let xs = [-2.0, 0.0, 1.5, 4.0, 10.0];
+    let ys: Vec<f64> = xs.iter().map(|x| 3.0 * x + 1.0).collect();
+    let (slope, intercept) = linear_regression(&xs, &ys).unwrap();
+    assert!((slope - 3.0).abs() < 1e-12);
+    assert!((intercept - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_linear_regression_too_few() {
+    assert_eq!(None, linear_regression(&[1.0], &[1.0]));
+    assert_eq!(None, linear_regression(&[1.0, 2.0], &[1.0]));
+}
+
+/// Geometric mean of input values, computed as
+/// `exp(mean(ln(x)))` to avoid overflowing the product. The
+/// geometric mean is undefined for an empty list or a list
+/// with any value that is not positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, geometric_mean(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, geometric_mean(&[1.0, 0.0]));
+/// ```
+#[cfg(feature = "std")]
+pub fn geometric_mean(nums: &[f64]) -> Option<f64> {
+    if nums.iter().any(|num| num.is_nan() || *num <= 0.0) {
+        return None;
+    }
+    let logs: Vec<f64> = nums.iter().map(|num| num.ln()).collect();
+    mean(&logs).map(f64::exp)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_geometric_mean_powers() {
+    let gm = geometric_mean(&[1.0, 4.0, 16.0]).unwrap();
+    assert!((gm - 4.0).abs() < 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_geometric_mean_negative() {
+    assert_eq!(None, geometric_mean(&[1.0, -4.0, 16.0]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_geometric_mean_large() {
+    // The naive product of these overflows to infinity.
+    let gm = geometric_mean(&[1e300, 1e300, 1e300]).unwrap();
+    assert!((gm / 1e300 - 1.0).abs() < 1e-12);
+}
+
+/// Geometric standard deviation of input values,
+/// `exp(stddev(ln(x)))`, a multiplicative factor of spread
+/// about the geometric mean. The geometric standard
+/// deviation is undefined for an empty list or a list with
+/// any value that is not positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, geometric_stddev(&[1.0, 0.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let gsd = geometric_stddev(&[1.0, 4.0]).unwrap();
+/// assert!((gsd - 2.0).abs() < 1e-12);
+/// ```
+#[cfg(feature = "std")]
+pub fn geometric_stddev(nums: &[f64]) -> Option<f64> {
+    if nums.iter().any(|num| num.is_nan() || *num <= 0.0) {
+        return None;
+    }
+    let logs: Vec<f64> = nums.iter().map(|num| num.ln()).collect();
+    stddev(&logs).map(f64::exp)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_geometric_stddev_logs() {
+    let nums = [2.0, 8.0, 4.0, 1.0, 16.0];
+    // The logs are 1, 3, 2, 0 and 4 times ln 2, whose
+    // standard deviation is sqrt(2) ln 2.
+    let expected = (2f64.sqrt() * 2f64.ln()).exp();
+    assert!((geometric_stddev(&nums).unwrap() - expected).abs() < 1e-12);
+    assert_eq!(Some(1.0), geometric_stddev(&[3.0, 3.0]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_geometric_stddev_invalid() {
+    assert_eq!(None, geometric_stddev(&[]));
+    assert_eq!(None, geometric_stddev(&[1.0, -4.0, 16.0]));
+    assert_eq!(None, geometric_stddev(&[1.0, f64::NAN]));
+}
+
+/// Harmonic mean of input values, `n / sum(1/x)`. The
+/// harmonic mean is undefined for an empty list or a list
+/// containing 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, harmonic_mean(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), harmonic_mean(&[2.0, 2.0]));
+/// ```
+pub fn harmonic_mean(nums: &[f64]) -> Option<f64> {
+    if nums.is_empty() || nums.contains(&0.0) {
+        return None;
+    }
+    let mut sum = 0.0;
+    for val in nums {
+        sum += 1.0 / val;
+    }
+    Some(nums.len() as f64 / sum)
+}
+
+#[test]
+fn test_harmonic_mean_rates() {
+    let hm = harmonic_mean(&[1.0, 2.0, 4.0]).unwrap();
+    assert!((hm - 12.0 / 7.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_harmonic_mean_zero() {
+    assert_eq!(None, harmonic_mean(&[1.0, 0.0, 4.0]));
+    assert_eq!(None, harmonic_mean(&[1.0, -0.0, 4.0]));
+}
+
+/// Weighted arithmetic mean of input values,
+/// `sum(w*x) / sum(w)`. The weighted mean is undefined for
+/// empty lists, lists of differing lengths, negative
+/// weights, or weights summing to zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_mean(&[1.0, 2.0], &[0.0, 0.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.5), weighted_mean(&[1.0, 2.0], &[1.0, 1.0]));
+/// ```
+pub fn weighted_mean(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    if nums.len() != weights.len() {
+        return None;
+    }
+    if weights.iter().any(|w| w.is_nan() || *w < 0.0) {
+        return None;
+    }
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    for (val, w) in nums.iter().zip(weights) {
+        sum += w * val;
+        total += w;
+    }
+    if total == 0.0 {
+        return None;
+    }
+    Some(sum / total)
+}
+
+#[test]
+fn test_weighted_mean_equal() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    assert_eq!(mean(&nums), weighted_mean(&nums, &[2.0; 5]));
+}
+
+#[test]
+fn test_weighted_mean_lopsided() {
+    assert_eq!(Some(3.0), weighted_mean(&[1.0, 2.0, 3.5], &[0.0, 1.0, 2.0]));
+}
+
+#[test]
+fn test_weighted_mean_invalid() {
+    assert_eq!(None, weighted_mean(&[], &[]));
+    assert_eq!(None, weighted_mean(&[1.0, 2.0], &[1.0]));
+    assert_eq!(None, weighted_mean(&[1.0, 2.0], &[2.0, -1.0]));
+}
+
+/// Weighted population variance of input values: the
+/// weighted mean of the squared deviations from the
+/// weighted mean. The weighted variance is undefined for
+/// empty lists, lists of differing lengths, negative
+/// weights, or weights summing to zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_variance(&[1.0, 2.0], &[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), weighted_variance(&[0.0, 3.0], &[2.0, 1.0]));
+/// ```
+pub fn weighted_variance(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    let xbar = weighted_mean(nums, weights)?;
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    for (val, w) in nums.iter().zip(weights) {
+        sum += w * (val - xbar) * (val - xbar);
+        total += w;
+    }
+    Some(sum / total)
+}
+
+#[test]
+fn test_weighted_variance_equal() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    let wv = weighted_variance(&nums, &[3.0; 5]).unwrap();
+    assert!((wv - variance(&nums).unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn test_weighted_variance_repeated() {
+    // Integer weights act as repeated values.
+    let wv = weighted_variance(&[1.0, 2.0, 4.0], &[2.0, 1.0, 3.0]).unwrap();
+    let repeated = variance(&[1.0, 1.0, 2.0, 4.0, 4.0, 4.0]).unwrap();
+    assert!((wv - repeated).abs() < 1e-12);
+}
+
+#[test]
+fn test_weighted_variance_invalid() {
+    assert_eq!(None, weighted_variance(&[], &[]));
+    assert_eq!(None, weighted_variance(&[1.0, 2.0], &[0.0, 0.0]));
+    assert_eq!(None, weighted_variance(&[1.0, 2.0], &[2.0, -1.0]));
+}
+
+/// Weighted population standard deviation of input values,
+/// the square root of `weighted_variance`. The weighted
+/// standard deviation is undefined for empty lists, lists
+/// of differing lengths, negative weights, or weights
+/// summing to zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_stddev(&[], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), weighted_stddev(&[0.0, 5.0], &[1.0, 4.0]));
+/// ```
+pub fn weighted_stddev(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    weighted_variance(nums, weights).map(|var| var.sqrt())
+}
+
+#[test]
+fn test_weighted_stddev_equal() {
+    let nums = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    assert_eq!(Some(2.0), weighted_stddev(&nums, &[0.5; 8]));
+}
+
+/// Weighted median of input values: the smallest value at
+/// which the cumulative weight of the sorted values reaches
+/// half the total weight. With equal weights this is the
+/// lower middle value, as for `median`. The weighted median
+/// is undefined for empty lists, lists of differing
+/// lengths, `NaN` values, negative weights, or weights
+/// summing to zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, weighted_median(&[1.0, 2.0], &[0.0, 0.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), weighted_median(&[1.0, 2.0, 3.0], &[1.0, 1.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn weighted_median(nums: &[f64], weights: &[f64]) -> Option<f64> {
+    if nums.len() != weights.len() || nums.iter().any(|val| val.is_nan()) {
+        return None;
+    }
+    if weights.iter().any(|w| w.is_nan() || *w < 0.0) {
+        return None;
+    }
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut pairs: Vec<(f64, f64)> = nums.iter().copied().zip(weights.iter().copied()).collect();
+    pairs.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let half = total / 2.0;
+    let mut cumulative = 0.0;
+    for &(val, w) in &pairs {
+        cumulative += w;
+        if cumulative >= half {
+            return Some(val);
+        }
+    }
+    pairs.last().map(|&(val, _)| val)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_weighted_median_equal() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0, 12.0];
+    assert_eq!(median(&nums), weighted_median(&nums, &[2.0; 6]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_weighted_median_shifted() {
+    let nums = [4.0, 1.0, 3.0, 2.0, 5.0];
+    assert_eq!(Some(3.0), median(&nums));
+    assert_eq!(
+        Some(2.0),
+        weighted_median(&nums, &[1.0, 1.0, 1.0, 4.0, 1.0])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_weighted_median_invalid() {
+    assert_eq!(None, weighted_median(&[], &[]));
+    assert_eq!(None, weighted_median(&[1.0, 2.0], &[1.0]));
+    assert_eq!(None, weighted_median(&[1.0, 2.0], &[2.0, -1.0]));
+    assert_eq!(None, weighted_median(&[1.0, f64::NAN], &[1.0, 1.0]));
+}
+
+/// Streaming accumulator of count, mean and variance, using
+/// Welford's online algorithm so that values can be pushed
+/// one at a time without being stored.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut stats = RunningStats::new();
+/// stats.push(-1.0);
+/// stats.push(3.0);
+/// assert_eq!(Some(1.0), stats.mean());
+/// assert_eq!(Some(2.0), stats.stddev());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    sum_sq_dev: f64,
+}
+
+impl RunningStats {
+    /// Make a new empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accumulate the value `x`.
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.sum_sq_dev += delta * (x - self.mean);
+    }
+
+    /// Number of values accumulated so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Arithmetic mean of the accumulated values. The mean
+    /// of no values is undefined.
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.mean)
+    }
+
+    /// Population variance of the accumulated values. The
+    /// variance of no values is undefined.
+    pub fn variance(&self) -> Option<f64> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.sum_sq_dev / self.count as f64)
+    }
+
+    /// Population standard deviation of the accumulated
+    /// values. The standard deviation of no values is
+    /// undefined.
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Combine this accumulator with `other`, as if every
+    /// value pushed to either had been pushed to one. Uses
+    /// the parallel variance formula of Chan et al., and
+    /// gives the same result in either order.
+    pub fn merge(&self, other: &RunningStats) -> RunningStats {
+        let count = self.count + other.count;
+        if count == 0 {
+            return RunningStats::new();
+        }
+        let (na, nb, n) = (self.count as f64, other.count as f64, count as f64);
+        let delta = other.mean - self.mean;
+        RunningStats {
+            count,
+            mean: (na * self.mean + nb * other.mean) / n,
+            sum_sq_dev: self.sum_sq_dev + other.sum_sq_dev + delta * delta * na * nb / n,
+        }
+    }
+}
+
+#[test]
+fn test_running_stats_matches() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    let mut stats = RunningStats::new();
+    for &num in &nums {
+        stats.push(num);
+    }
+    assert_eq!(5, stats.count());
+    assert_eq!(Some(100.0), stats.mean());
+    assert_eq!(97.0, stats.stddev().unwrap().round());
+    assert_eq!(variance(&nums), stats.variance());
+}
+
+#[test]
+fn test_running_stats_empty() {
+    let stats = RunningStats::new();
+    assert_eq!(0, stats.count());
+    assert_eq!(None, stats.mean());
+    assert_eq!(None, stats.variance());
+    assert_eq!(None, stats.stddev());
+}
+
+#[test]
+fn test_running_stats_merge() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0, 12.25, 3.0];
+    let (left, right) = nums.split_at(3);
+    let mut a = RunningStats::new();
+    for &num in left {
+        a.push(num);
+    }
+    let mut b = RunningStats::new();
+    for &num in right {
+        b.push(num);
+    }
+    let ab = a.merge(&b);
+    assert_eq!(ab, b.merge(&a));
+    assert_eq!(nums.len(), ab.count());
+    assert!((ab.mean().unwrap() - mean(&nums).unwrap()).abs() < 1e-9);
+    assert!((ab.variance().unwrap() - variance(&nums).unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn test_running_stats_merge_empty() {
+    let mut a = RunningStats::new();
+    a.push(2.0);
+    assert_eq!(a, a.merge(&RunningStats::new()));
+    assert_eq!(a, RunningStats::new().merge(&a));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_running_stats_serde_round_trip() {
+    let mut stats = RunningStats::new();
+    stats.push(-1.0);
+    stats.push(3.0);
+    let json = serde_json::to_string(&stats).unwrap();
+    assert_eq!(stats, serde_json::from_str(&json).unwrap());
+}
+
+/// Accumulate all the values of an iterator, as by `push`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut stats = RunningStats::new();
+/// stats.extend(vec![-1.0, 3.0]);
+/// assert_eq!(Some(1.0), stats.mean());
+/// ```
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Make an accumulator of all the values of an iterator.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let stats: RunningStats = vec![-1.0, 3.0].into_iter().collect();
+/// assert_eq!(Some(1.0), stats.mean());
+/// ```
+impl core::iter::FromIterator<f64> for RunningStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = RunningStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+#[test]
+fn test_running_stats_collect() {
+    let nums: Vec<f64> = (1..=100).map(f64::from).collect();
+    let stats: RunningStats = (1..=100).map(f64::from).collect();
+    assert_eq!(nums.len(), stats.count());
+    assert!((stats.mean().unwrap() - mean(&nums).unwrap()).abs() < 1e-12);
+    assert!((stats.variance().unwrap() - variance(&nums).unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn test_running_stats_extend_chunks() {
+    let mut chunked = RunningStats::new();
+    chunked.extend(vec![75.5, 100.5, 95.5]);
+    chunked.extend([265.5, -37.0].iter().copied());
+    let mut whole = RunningStats::new();
+    for &x in &[75.5, 100.5, 95.5, 265.5, -37.0] {
+        whole.push(x);
+    }
+    assert_eq!(whole, chunked);
+}
+
+/// Five-number summary of a list of values, along with its
+/// mean and count. The quartiles are interpolated as for
+/// `quantile`, so `median` here is `quantile(nums, 0.5)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub count: usize,
+}
+
+/// Summary of input values, sorting them only once. The
+/// summary is undefined for an empty list or a list
+/// containing `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, summary(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let s = summary(&[3.0, 1.0, 2.0]).unwrap();
+/// assert_eq!((1.0, 2.0, 3.0), (s.min, s.median, s.max));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn summary(nums: &[f64]) -> Option<Summary> {
+    let sorted = sorted(nums)?;
+    Some(Summary {
+        min: *sorted.first()?,
+        q1: sorted_quantile(&sorted, 0.25)?,
+        median: sorted_quantile(&sorted, 0.5)?,
+        q3: sorted_quantile(&sorted, 0.75)?,
+        max: *sorted.last()?,
+        mean: mean(&sorted)?,
+        count: sorted.len(),
+    })
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_summary_fields() {
+    let s = summary(&[7.0, 1.0, 5.0, 3.0, 9.0, 2.0, 8.0, 4.0, 6.0]).unwrap();
+    assert_eq!(
+        Summary {
+            min: 1.0,
+            q1: 3.0,
+            median: 5.0,
+            q3: 7.0,
+            max: 9.0,
+            mean: 5.0,
+            count: 9,
+        },
+        s
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_summary_nan() {
+    assert_eq!(None, summary(&[1.0, f64::NAN]));
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[test]
+fn test_summary_serde_round_trip() {
+    let s = summary(&[7.5, 1.0, 5.25, 3.0, -9.0]).unwrap();
+    let json = serde_json::to_string(&s).unwrap();
+    assert!(json.contains("\"median\":3.0"));
+    assert_eq!(s, serde_json::from_str(&json).unwrap());
+}
+
+/// Arithmetic mean of the values of `iter`, as for `mean`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), mean_iter((1..=5).map(f64::from)));
+/// ```
+pub fn mean_iter<I: IntoIterator<Item = f64>>(iter: I) -> Option<f64> {
+    let mut count = 0;
+    let sum = compensated_sum_iter(iter.into_iter().inspect(|_| count += 1));
+    if count == 0 {
+        return None;
+    }
+    Some(sum / count as f64)
+}
+
+#[test]
+fn test_mean_iter_matches() {
+    let nums: Vec<f64> = (1..=5).map(f64::from).collect();
+    assert_eq!(mean(&nums), mean_iter((1..=5).map(f64::from)));
+    assert_eq!(None, mean_iter(Vec::new()));
+    // Naive summation would lose every 1.0 added to 1e16.
+    let mut nums = vec![1e16];
+    nums.resize(10_001, 1.0);
+    assert_eq!(mean(&nums), mean_iter(nums.iter().copied()));
+    assert_eq!(kahan_sum(&nums), sum_iter(nums.iter().copied()));
+}
+
+/// Sum of the values of `iter`, using Kahan compensated
+/// summation as for `kahan_sum`. The sum of no values is
+/// 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(15.0, sum_iter((1..=5).map(f64::from)));
+/// ```
+pub fn sum_iter<I: IntoIterator<Item = f64>>(iter: I) -> f64 {
+    compensated_sum_iter(iter.into_iter())
+}
+
+#[test]
+fn test_sum_iter_empty() {
+    assert_eq!(0.0, sum_iter(Vec::new()));
+}
+
+/// Minimum of the values of `iter`, skipping `NaN`, as for
+/// `min`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), min_iter((1..=5).map(f64::from)));
+/// ```
+pub fn min_iter<I: IntoIterator<Item = f64>>(iter: I) -> Option<f64> {
+    let result = iter.into_iter().fold(f64::NAN, f64::min);
+    if result.is_nan() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+#[test]
+fn test_min_iter_matches() {
+    let nums: Vec<f64> = (1..=5).map(f64::from).collect();
+    assert_eq!(min(&nums), min_iter((1..=5).map(f64::from)));
+    assert_eq!(None, min_iter(vec![f64::NAN]));
+}
+
+/// Maximum of the values of `iter`, skipping `NaN`, as for
+/// `max`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), max_iter((1..=5).map(f64::from)));
+/// ```
+pub fn max_iter<I: IntoIterator<Item = f64>>(iter: I) -> Option<f64> {
+    let result = iter.into_iter().fold(f64::NAN, f64::max);
+    if result.is_nan() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+#[test]
+fn test_max_iter_matches() {
+    let nums: Vec<f64> = (1..=5).map(f64::from).collect();
+    assert_eq!(max(&nums), max_iter((1..=5).map(f64::from)));
+    assert_eq!(None, max_iter(Vec::new()));
+}
+
+/// L1 norm of the values of `iter`, as for `l1`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), l1_iter(vec![-3.0, 4.0]));
+/// ```
+pub fn l1_iter<I: IntoIterator<Item = f64>>(iter: I) -> Option<f64> {
+    Some(sum_iter(iter.into_iter().map(f64::abs)))
+}
+
+#[test]
+fn test_l1_iter_matches() {
+    let nums: Vec<f64> = (1..=5).map(f64::from).collect();
+    assert_eq!(l1(&nums), l1_iter((1..=5).map(f64::from)));
+}
+
+/// L2 norm of the values of `iter`, as for `l2`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), l2_iter(vec![-3.0, 4.0]));
+/// ```
+pub fn l2_iter<I: IntoIterator<Item = f64>>(iter: I) -> Option<f64> {
+    Some(scaled_norm(iter.into_iter()))
+}
+
+#[test]
+fn test_l2_iter_matches() {
+    let nums: Vec<f64> = (1..=5).map(f64::from).collect();
+    assert_eq!(l2(&nums), l2_iter((1..=5).map(f64::from)));
+}
+
+/// How policy-aware statistics treat `NaN` input values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NanPolicy {
+    /// Drop `NaN` values before computing the statistic.
+    Skip,
+    /// Treat the statistic as undefined if any value is
+    /// `NaN`.
+    Propagate,
+}
+
+/// Apply `stat` to input values according to `policy`.
+#[cfg(feature = "alloc")]
+fn with_policy(nums: &[f64], policy: NanPolicy, stat: StatFn) -> Option<f64> {
+    match policy {
+        NanPolicy::Skip => {
+            let nums: Vec<f64> = nums.iter().copied().filter(|num| !num.is_nan()).collect();
+            stat(&nums)
+        }
+        NanPolicy::Propagate => {
+            if nums.iter().any(|num| num.is_nan()) {
+                None
+            } else {
+                stat(nums)
+            }
+        }
+    }
+}
+
+/// Arithmetic mean of input values, treating `NaN`
+/// according to `policy`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), mean_with(&[1.0, f64::NAN, 3.0], NanPolicy::Skip));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mean_with(nums: &[f64], policy: NanPolicy) -> Option<f64> {
+    with_policy(nums, policy, mean)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mean_with_policies() {
+    let nums = [1.0, f64::NAN, 3.0];
+    assert_eq!(Some(2.0), mean_with(&nums, NanPolicy::Skip));
+    assert_eq!(None, mean_with(&nums, NanPolicy::Propagate));
+}
+
+/// Population standard deviation of input values, treating
+/// `NaN` according to `policy`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, stddev_with(&[1.0, f64::NAN, 3.0], NanPolicy::Propagate));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn stddev_with(nums: &[f64], policy: NanPolicy) -> Option<f64> {
+    with_policy(nums, policy, stddev)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_stddev_with_policies() {
+    let nums = [-1.0, f64::NAN, 3.0];
+    assert_eq!(Some(2.0), stddev_with(&nums, NanPolicy::Skip));
+    assert_eq!(None, stddev_with(&nums, NanPolicy::Propagate));
+}
+
+/// Minimum of input values, treating `NaN` according to
+/// `policy`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), min_with(&[1.0, f64::NAN, 3.0], NanPolicy::Skip));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn min_with(nums: &[f64], policy: NanPolicy) -> Option<f64> {
+    with_policy(nums, policy, min)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_min_with_policies() {
+    let nums = [1.0, f64::NAN, 3.0];
+    assert_eq!(Some(1.0), min_with(&nums, NanPolicy::Skip));
+    assert_eq!(None, min_with(&nums, NanPolicy::Propagate));
+}
+
+/// Maximum of input values, treating `NaN` according to
+/// `policy`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3.0), max_with(&[1.0, f64::NAN, 3.0], NanPolicy::Skip));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn max_with(nums: &[f64], policy: NanPolicy) -> Option<f64> {
+    with_policy(nums, policy, max)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_max_with_policies() {
+    let nums = [1.0, f64::NAN, 3.0];
+    assert_eq!(Some(3.0), max_with(&nums, NanPolicy::Skip));
+    assert_eq!(None, max_with(&nums, NanPolicy::Propagate));
+}
+
+/// Median value of input values, as for `median`, treating
+/// `NaN` according to `policy`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), median_with(&[1.0, f64::NAN, 3.0], NanPolicy::Skip));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn median_with(nums: &[f64], policy: NanPolicy) -> Option<f64> {
+    with_policy(nums, policy, median)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_with_policies() {
+    let nums = [1.0, f64::NAN, 3.0, 2.0];
+    assert_eq!(Some(2.0), median_with(&nums, NanPolicy::Skip));
+    assert_eq!(None, median_with(&nums, NanPolicy::Propagate));
+}
+
+/// Number of values to cut from each end of a sorted list
+/// of `count` values when trimming `proportion` of them,
+/// for `proportion` in `[0.0, 0.5)`.
+#[cfg(feature = "alloc")]
+fn trim_count(count: usize, proportion: f64) -> Option<usize> {
+    if !(0.0..0.5).contains(&proportion) {
+        return None;
+    }
+    let cut = (proportion * count as f64) as usize;
+    if 2 * cut >= count {
+        return None;
+    }
+    Some(cut)
+}
+
+/// Trimmed mean of input values: the mean after dropping
+/// `proportion` of the sorted values from each end, for
+/// `proportion` in `[0.0, 0.5)`. The trimmed mean is
+/// undefined for an empty list, a list containing `NaN`,
+/// or an out-of-range `proportion`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, trimmed_mean(&[1.0, 2.0], 0.5));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), trimmed_mean(&[1.0, 2.0, 3.0], 0.0));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn trimmed_mean(nums: &[f64], proportion: f64) -> Option<f64> {
+    let nums = sorted(nums)?;
+    let cut = trim_count(nums.len(), proportion)?;
+    mean(&nums[cut..nums.len() - cut])
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_trimmed_mean_outliers() {
+    assert_eq!(Some(3.0), trimmed_mean(&[100.0, 2.0, 3.0, 4.0, 1.0], 0.2));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_trimmed_mean_invalid() {
+    assert_eq!(None, trimmed_mean(&[], 0.1));
+    assert_eq!(None, trimmed_mean(&[1.0, 2.0], -0.1));
+    assert_eq!(None, trimmed_mean(&[1.0, f64::NAN], 0.1));
+}
+
+/// Winsorized mean of input values: the mean after
+/// replacing `proportion` of the sorted values at each end
+/// with the nearest value that is kept, for `proportion` in
+/// `[0.0, 0.5)`. The winsorized mean is undefined for the
+/// same inputs as `trimmed_mean`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, winsorized_mean(&[], 0.2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), winsorized_mean(&[1.0, 2.0, 9.0], 0.4));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn winsorized_mean(nums: &[f64], proportion: f64) -> Option<f64> {
+    let mut nums = sorted(nums)?;
+    let cut = trim_count(nums.len(), proportion)?;
+    let last = nums.len() - 1;
+    let (lo, hi) = (nums[cut], nums[last - cut]);
+    for val in &mut nums[..cut] {
+        *val = lo;
+    }
+    for val in &mut nums[last - cut + 1..] {
+        *val = hi;
+    }
+    mean(&nums)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_winsorized_mean_outliers() {
+    let nums = [100.0, 2.0, 3.0, 4.0, 1.0];
+    // Winsorizing gives [2, 2, 3, 4, 4] and trimming [2, 3, 4].
+    assert_eq!(Some(3.0), trimmed_mean(&nums, 0.2));
+    assert_eq!(Some(3.0), winsorized_mean(&nums, 0.2));
+    let nums = [0.0, 2.0, 3.0, 7.0, 100.0];
+    assert_eq!(Some(4.0), trimmed_mean(&nums, 0.2));
+    assert_eq!(Some(4.2), winsorized_mean(&nums, 0.2));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_winsorized_mean_invalid() {
+    assert_eq!(None, winsorized_mean(&[1.0, 2.0], 0.5));
+    assert_eq!(None, winsorized_mean(&[1.0, f64::NAN], 0.1));
+}
+
+/// Mean absolute deviation of input values from their
+/// mean, `mean(|x - mean|)`. The mean absolute deviation of
+/// an empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, mean_absolute_deviation(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), mean_absolute_deviation(&[-1.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mean_absolute_deviation(nums: &[f64]) -> Option<f64> {
+    let xbar = mean(nums)?;
+    let devs: Vec<f64> = nums.iter().map(|num| (num - xbar).abs()).collect();
+    mean(&devs)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mean_absolute_deviation_known() {
+    assert_eq!(
+        Some(31.2),
+        mean_absolute_deviation(&[1.0, 2.0, 3.0, 4.0, 100.0])
+    );
+}
+
+/// Median absolute deviation of input values from their
+/// median, `median(|x - median|)`, using `median`'s
+/// tie-breaking. The median absolute deviation is undefined
+/// for an empty list or a list containing `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median_absolute_deviation(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), median_absolute_deviation(&[-1.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn median_absolute_deviation(nums: &[f64]) -> Option<f64> {
+    let med = median(nums)?;
+    let devs: Vec<f64> = nums.iter().map(|num| (num - med).abs()).collect();
+    median(&devs)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_absolute_deviation_known() {
+    assert_eq!(
+        Some(1.0),
+        median_absolute_deviation(&[1.0, 2.0, 3.0, 4.0, 100.0])
+    );
+}
+
+/// Coefficient of variation of input values, the ratio of
+/// the population standard deviation to the mean. The
+/// coefficient of variation is undefined for an empty list
+/// or a list with mean 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, coefficient_of_variation(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.5), coefficient_of_variation(&[1.0, 3.0]));
+/// ```
+pub fn coefficient_of_variation(nums: &[f64]) -> Option<f64> {
+    let xbar = mean(nums)?;
+    if xbar == 0.0 {
+        return None;
+    }
+    Some(stddev(nums)? / xbar)
+}
+
+#[test]
+fn test_coefficient_of_variation_positive() {
+    assert_eq!(
+        Some(0.4),
+        coefficient_of_variation(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0])
+    );
+}
+
+#[test]
+fn test_coefficient_of_variation_zero_mean() {
+    assert_eq!(None, coefficient_of_variation(&[-1.0, 1.0]));
+}
+
+/// Standard error of the mean of input values,
+/// `sample_stddev / sqrt(n)`. The standard error of a list
+/// with fewer than two elements is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, standard_error(&[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), standard_error(&[-1.0, 3.0]));
+/// ```
+pub fn standard_error(nums: &[f64]) -> Option<f64> {
+    let count = nums.len() as f64;
+    sample_stddev(nums).map(|sigma| sigma / count.sqrt())
+}
+
+#[test]
+fn test_standard_error_known() {
+    // The sum of squared deviations is 32, so the standard
+    // error is sqrt(32 / 7 / 8).
+    let se = standard_error(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+    assert!((se - (4.0f64 / 7.0).sqrt()).abs() < 1e-12);
+}
+
+/// Product of input values. The product of an empty list is
+/// 1.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), product(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-6.0), product(&[-2.0, 3.0]));
+/// ```
+pub fn product(nums: &[f64]) -> Option<f64> {
+    let mut prod = 1.0;
+    for val in nums {
+        prod *= val;
+    }
+    Some(prod)
+}
+
+#[test]
+fn test_product_three() {
+    assert_eq!(Some(24.0), product(&[2.0, 3.0, 4.0]));
+}
+
+#[test]
+fn test_product_empty() {
+    assert_eq!(Some(1.0), product(&[]));
+}
+
+/// Cumulative (prefix) sums of input values: element `i` of
+/// the result is the sum of `nums[0..=i]`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(cumulative_sum(&[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![-1.0, 0.0], cumulative_sum(&[-1.0, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cumulative_sum(nums: &[f64]) -> Vec<f64> {
+    let mut sum = 0.0;
+    nums.iter()
+        .map(|num| {
+            sum += num;
+            sum
+        })
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cumulative_sum_three() {
+    assert_eq!(vec![1.0, 3.0, 6.0], cumulative_sum(&[1.0, 2.0, 3.0]));
+}
+
+/// Cumulative (prefix) maxima of input values: element `i`
+/// of the result is the maximum of `nums[0..=i]`, skipping
+/// `NaN` as `max` does. Elements before the first non-`NaN`
+/// value are `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(cumulative_max(&[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![1.0, 1.0, 2.0], cumulative_max(&[1.0, f64::NAN, 2.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cumulative_max(nums: &[f64]) -> Vec<f64> {
+    let mut max = f64::NAN;
+    nums.iter()
+        .map(|&num| {
+            max = max.max(num);
+            max
+        })
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cumulative_max_five() {
+    assert_eq!(
+        vec![1.0, 3.0, 3.0, 5.0, 5.0],
+        cumulative_max(&[1.0, 3.0, 2.0, 5.0, 4.0])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cumulative_max_leading_nan() {
+    let maxima = cumulative_max(&[f64::NAN, 2.0, 1.0]);
+    assert!(maxima[0].is_nan());
+    assert_eq!(vec![2.0, 2.0], maxima[1..]);
+}
+
+/// Cumulative (prefix) minima of input values: element `i`
+/// of the result is the minimum of `nums[0..=i]`, skipping
+/// `NaN` as `min` does. Elements before the first non-`NaN`
+/// value are `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(cumulative_min(&[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![2.0, 2.0, 1.0], cumulative_min(&[2.0, f64::NAN, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cumulative_min(nums: &[f64]) -> Vec<f64> {
+    let mut min = f64::NAN;
+    nums.iter()
+        .map(|&num| {
+            min = min.min(num);
+            min
+        })
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cumulative_min_five() {
+    assert_eq!(
+        vec![1.0, 1.0, 1.0, 1.0, 1.0],
+        cumulative_min(&[1.0, 3.0, 2.0, 5.0, 4.0])
+    );
+    assert_eq!(
+        vec![4.0, 4.0, 2.0, 2.0, 1.0],
+        cumulative_min(&[4.0, 5.0, 2.0, 3.0, 1.0])
+    );
+}
+
+/// First differences of input values: element `i` of the
+/// result is `nums[i + 1] - nums[i]`, giving
+/// `nums.len() - 1` results, or none for fewer than two
+/// values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(diff(&[1.0]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![2.0, -3.0], diff(&[1.0, 3.0, 0.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn diff(nums: &[f64]) -> Vec<f64> {
+    nums.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_diff_squares() {
+    assert_eq!(vec![3.0, 5.0, 7.0], diff(&[1.0, 4.0, 9.0, 16.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_diff_short() {
+    assert!(diff(&[]).is_empty());
+    assert!(diff(&[2.0]).is_empty());
+}
+
+/// Simple moving average of input values: the mean of each
+/// consecutive `window` values, giving
+/// `nums.len() - window + 1` results. The moving average is
+/// undefined for a `window` of 0 or one longer than the
+/// list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, moving_average(&[1.0, 2.0], 3));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![1.5, 2.5]), moving_average(&[1.0, 2.0, 3.0], 2));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn moving_average(nums: &[f64], window: usize) -> Option<Vec<f64>> {
+    if window == 0 || window > nums.len() {
+        return None;
+    }
+    let size = window as f64;
+    let window_sum = |vals: &[f64]| {
+        vals.iter()
+            .fold((0.0, 0.0), |acc, &val| neumaier_add(acc, val))
+    };
+    let average = |(sum, compensation): (f64, f64)| {
+        // The compensation is meaningless once the sum is not
+        // finite.
+        if sum.is_finite() {
+            (sum + compensation) / size
+        } else {
+            sum / size
+        }
+    };
+    // Slide the window sum along rather than re-adding
+    // every window, keeping the rounding error so that a
+    // spike leaving the window takes nothing else with it.
+    let mut acc = window_sum(&nums[..window]);
+    let mut averages = vec![average(acc)];
+    for i in window..nums.len() {
+        acc = neumaier_add(neumaier_add(acc, nums[i]), -nums[i - window]);
+        // A `NaN` or infinity poisons the running sum even
+        // after it leaves, so re-add the window instead.
+        if !acc.0.is_finite() {
+            acc = window_sum(&nums[i + 1 - window..=i]);
+        }
+        averages.push(average(acc));
+    }
+    Some(averages)
+}
+
+/// Add `val` to the `(sum, compensation)` pair of a
+/// Neumaier compensated sum, whose total is
+/// `sum + compensation`. Unlike plain Kahan summation this
+/// keeps the low-order bits of `val` when it is smaller
+/// than the sum, and of the sum when it is not.
+#[cfg(feature = "alloc")]
+fn neumaier_add((sum, compensation): (f64, f64), val: f64) -> (f64, f64) {
+    let t = sum + val;
+    let error = if sum.abs() >= val.abs() {
+        (sum - t) + val
+    } else {
+        (val - t) + sum
+    };
+    (t, compensation + error)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_moving_average_three() {
+    assert_eq!(
+        Some(vec![2.0, 3.0, 4.0]),
+        moving_average(&[1.0, 2.0, 3.0, 4.0, 5.0], 3)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_moving_average_invalid() {
+    assert_eq!(None, moving_average(&[1.0, 2.0], 0));
+    assert_eq!(None, moving_average(&[], 1));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_moving_average_non_finite() {
+    let averages = moving_average(&[f64::NAN, 1.0, 2.0, 3.0], 1).unwrap();
+    assert!(averages[0].is_nan());
+    assert_eq!(&[1.0, 2.0, 3.0], &averages[1..]);
+    assert_eq!(
+        Some(vec![f64::INFINITY, 1.5, 2.5]),
+        moving_average(&[f64::INFINITY, 1.0, 2.0, 3.0], 2)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_moving_average_spike() {
+    assert_eq!(
+        Some(vec![1e17, 1.0, 1.0, 1.0, 1.0]),
+        moving_average(&[1e17, 1.0, 1.0, 1.0, 1.0], 1)
+    );
+    assert_eq!(
+        Some(vec![5e16, 1.5, 2.5]),
+        moving_average(&[1e17, 1.0, 2.0, 3.0], 2)
+    );
+}
+
+/// Rolling population standard deviation of input values:
+/// the standard deviation of each consecutive `window`
+/// values, giving `nums.len() - window + 1` results. The
+/// rolling standard deviation is undefined for a `window`
+/// of 0 or one longer than the list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, rolling_stddev(&[1.0, 2.0], 0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![1.0, 0.0]), rolling_stddev(&[1.0, 3.0, 3.0], 2));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn rolling_stddev(nums: &[f64], window: usize) -> Option<Vec<f64>> {
+    if window == 0 || window > nums.len() {
+        return None;
+    }
+    // Each window is recomputed as for `stddev`: a sliding
+    // sum of squares cancels badly after a spike or on a
+    // large offset, and would hold on to a `NaN` forever.
+    Some(
+        nums.windows(window)
+            .map(|vals| stddev(vals).unwrap())
+            .collect(),
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rolling_stddev_three() {
+    let nums = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let rolling = rolling_stddev(&nums, 3).unwrap();
+    assert_eq!(nums.len() - 2, rolling.len());
+    for (i, sd) in rolling.iter().enumerate() {
+        assert!((sd - stddev(&nums[i..i + 3]).unwrap()).abs() < 1e-12);
+    }
+    assert_eq!(Some(vec![2.0]), rolling_stddev(&nums, 8));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rolling_stddev_invalid() {
+    assert_eq!(None, rolling_stddev(&[1.0, 2.0], 0));
+    assert_eq!(None, rolling_stddev(&[1.0, 2.0], 3));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rolling_stddev_spike() {
+    assert_eq!(
+        Some(vec![5e7 - 0.5, 0.5, 0.5, 0.5]),
+        rolling_stddev(&[1e8, 1.0, 2.0, 3.0, 4.0], 2)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rolling_stddev_offset() {
+    let nums = [1e9, 1e9 + 1.0, 1e9 + 2.0, 1e9 + 3.0];
+    assert_eq!(Some(vec![0.5, 0.5, 0.5]), rolling_stddev(&nums, 2));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rolling_stddev_nan() {
+    let rolling = rolling_stddev(&[f64::NAN, 1.0, 2.0, 3.0], 2).unwrap();
+    assert!(rolling[0].is_nan());
+    assert_eq!(&[0.5, 0.5], &rolling[1..]);
+}
+
+/// Exponential moving average of input values with
+/// smoothing factor `alpha` in `(0.0, 1.0]`: each result is
+/// `alpha * x + (1 - alpha) * previous`, starting from the
+/// first value. The average is undefined for an empty list
+/// or an out-of-range `alpha`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, exponential_moving_average(&[1.0], 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Some(vec![0.0, 2.0, 3.0]),
+///     exponential_moving_average(&[0.0, 4.0, 4.0], 0.5)
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn exponential_moving_average(nums: &[f64], alpha: f64) -> Option<Vec<f64>> {
+    if nums.is_empty() || !(alpha > 0.0 && alpha <= 1.0) {
+        return None;
+    }
+    let mut prev = nums[0];
+    Some(
+        nums.iter()
+            .map(|num| {
+                prev = alpha * num + (1.0 - alpha) * prev;
+                prev
+            })
+            .collect(),
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_exponential_moving_average_tracking() {
+    let nums = [0.0, 10.0, 10.0, 10.0, 0.0];
+    let fast = exponential_moving_average(&nums, 0.9).unwrap();
+    let slow = exponential_moving_average(&nums, 0.1).unwrap();
+    for i in 0..nums.len() {
+        assert!((fast[i] - nums[i]).abs() <= (slow[i] - nums[i]).abs());
+    }
+    assert!((fast[3] - 10.0).abs() < 0.01);
+    assert!(slow[3] < 3.0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_exponential_moving_average_invalid() {
+    assert_eq!(None, exponential_moving_average(&[], 0.5));
+    assert_eq!(None, exponential_moving_average(&[1.0], 1.5));
+    assert_eq!(
+        Some(vec![1.0, 2.0]),
+        exponential_moving_average(&[1.0, 2.0], 1.0)
+    );
+}
+
+/// Standard scores of input values, `(x - mean) / stddev`
+/// using the population standard deviation. The scores are
+/// undefined for an empty list or a list with zero standard
+/// deviation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, z_scores(&[1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![-1.0, 1.0]), z_scores(&[-1.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn z_scores(nums: &[f64]) -> Option<Vec<f64>> {
+    let xbar = mean(nums)?;
+    let sigma = stddev(nums)?;
+    if sigma == 0.0 {
+        return None;
+    }
+    Some(nums.iter().map(|num| (num - xbar) / sigma).collect())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_z_scores_standardized() {
+    let z = z_scores(&[75.5, 100.5, 95.5, 265.5, -37.0]).unwrap();
+    assert_eq!(5, z.len());
+    assert!(mean(&z).unwrap().abs() < 1e-12);
+    assert!((stddev(&z).unwrap() - 1.0).abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_z_scores_empty() {
+    assert_eq!(None, z_scores(&[]));
+}
+
+/// Input values rescaled into `[0.0, 1.0]` by
+/// `(x - min) / (max - min)`, with `min` and `max` as for
+/// those functions. The scaling is undefined for an empty
+/// list or a list whose values are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, min_max_scale(&[2.0, 2.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![0.0, 0.25, 1.0]), min_max_scale(&[-1.0, 0.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn min_max_scale(nums: &[f64]) -> Option<Vec<f64>> {
+    let lo = min(nums)?;
+    let width = range(nums)?;
+    if width == 0.0 {
+        return None;
+    }
+    Some(nums.iter().map(|num| (num - lo) / width).collect())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_min_max_scale_endpoints() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    let scaled = min_max_scale(&nums).unwrap();
+    assert_eq!(0.0, scaled[4]);
+    assert_eq!(1.0, scaled[3]);
+    assert!(scaled.iter().all(|x| (0.0..=1.0).contains(x)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_min_max_scale_empty() {
+    assert_eq!(None, min_max_scale(&[]));
+}
+
+/// Outliers among input values by Tukey's rule: the values
+/// outside `[Q1 - k*IQR, Q3 + k*IQR]`, in input order, with
+/// quartiles interpolated as for `quantile`. An empty list,
+/// or a list containing `NaN`, has no outliers.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![50.0], iqr_outliers(&[1.0, 2.0, 3.0, 50.0], 1.5));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn iqr_outliers(nums: &[f64], k: f64) -> Vec<f64> {
+    let sorted = match sorted(nums) {
+        Some(sorted) => sorted,
+        None => return Vec::new(),
+    };
+    let (q1, q3) = match (
+        sorted_quantile(&sorted, 0.25),
+        sorted_quantile(&sorted, 0.75),
+    ) {
+        (Some(q1), Some(q3)) => (q1, q3),
+        _ => return Vec::new(),
+    };
+    let lo = q1 - k * (q3 - q1);
+    let hi = q3 + k * (q3 - q1);
+    nums.iter()
+        .copied()
+        .filter(|num| *num < lo || *num > hi)
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iqr_outliers_extreme() {
+    let nums = [10.0, 10.5, 9.5, 10.2, 9.8, 100.0, 10.1, 9.9];
+    assert_eq!(vec![100.0], iqr_outliers(&nums, 1.5));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iqr_outliers_empty() {
+    assert!(iqr_outliers(&[], 1.5).is_empty());
+}
+
+/// Outliers among input values by standard score: the
+/// values whose absolute z-score, as for `z_scores`, exceeds
+/// `threshold`, in input order. An empty list, or a list
+/// with zero standard deviation, has no outliers.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(zscore_outliers(&[1.0, 1.0], 3.0).is_empty());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn zscore_outliers(nums: &[f64], threshold: f64) -> Vec<f64> {
+    match z_scores(nums) {
+        Some(z) => nums
+            .iter()
+            .zip(z)
+            .filter(|(_, score)| score.abs() > threshold)
+            .map(|(&num, _)| num)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_zscore_outliers_injected() {
+    // Alternating +-1 has mean 0 and standard deviation 1.
+    let mut nums: Vec<f64> = (0..100)
+        .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+        .collect();
+    nums.push(5.0);
+    assert_eq!(vec![5.0], zscore_outliers(&nums, 3.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_zscore_outliers_empty() {
+    assert!(zscore_outliers(&[], 3.0).is_empty());
+}
+
+/// Histogram of input values over `bins` equal-width bins
+/// spanning `min..max`, as `(bin_start, bin_end, count)`.
+/// Each bin includes its lower edge, and the last bin also
+/// includes its upper edge so that the maximum is counted.
+/// `NaN` values are not counted, and if every value is
+/// equal they all fall in the first bin. The histogram is
+/// undefined for an empty list, zero bins, or a list
+/// containing an infinity, which no finite bin could hold.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, histogram(&[], 2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Some(vec![(0.0, 1.0, 1), (1.0, 2.0, 2)]),
+///     histogram(&[0.0, 1.0, 2.0], 2)
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn histogram(nums: &[f64], bins: usize) -> Option<Vec<(f64, f64, usize)>> {
+    if bins == 0 {
+        return None;
+    }
+    let lo = min(nums)?;
+    let hi = max(nums)?;
+    if !lo.is_finite() || !hi.is_finite() {
+        return None;
+    }
+    let width = (hi - lo) / bins as f64;
+    let mut counts = vec![0; bins];
+    for num in nums.iter().filter(|num| !num.is_nan()) {
+        let index = if width == 0.0 {
+            0
+        } else {
+            (((num - lo) / width) as usize).min(bins - 1)
+        };
+        counts[index] += 1;
+    }
+    Some(
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let start = lo + i as f64 * width;
+                let end = if i == bins - 1 {
+                    hi
+                } else {
+                    lo + (i + 1) as f64 * width
+                };
+                (start, end, count)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_histogram_uniform() {
+    let nums: Vec<f64> = (0..10).map(f64::from).collect();
+    let hist = histogram(&nums, 5).unwrap();
+    assert_eq!(5, hist.len());
+    assert!(hist.iter().all(|&(_, _, count)| count == 2));
+    assert_eq!(0.0, hist[0].0);
+    assert_eq!(9.0, hist[4].1);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_histogram_constant() {
+    assert_eq!(
+        Some(vec![(3.0, 3.0, 2), (3.0, 3.0, 0)]),
+        histogram(&[3.0, 3.0], 2)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_histogram_zero_bins() {
+    assert_eq!(None, histogram(&[1.0, 2.0], 0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_histogram_infinite() {
+    assert_eq!(None, histogram(&[1.0, f64::INFINITY], 2));
+    assert_eq!(None, histogram(&[f64::NEG_INFINITY, 1.0], 2));
+    assert_eq!(None, histogram(&[f64::INFINITY], 1));
+    assert_eq!(Some(vec![(1.0, 1.0, 1)]), histogram(&[f64::NAN, 1.0], 1));
+}
+
+/// Each distinct input value with the number of times it
+/// occurs, in ascending order of value. `NaN` values are
+/// left out of the table.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(frequency_table(&[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![(1.0, 1), (2.0, 1)], frequency_table(&[2.0, f64::NAN, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn frequency_table(nums: &[f64]) -> Vec<(f64, usize)> {
+    let nums: Vec<f64> = nums.iter().copied().filter(|num| !num.is_nan()).collect();
+    let nums = sorted(&nums).unwrap();
+    let mut table: Vec<(f64, usize)> = Vec::new();
+    for num in nums {
+        match table.last_mut() {
+            Some((value, count)) if *value == num => *count += 1,
+            _ => table.push((num, 1)),
+        }
+    }
+    table
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_frequency_table_counts() {
+    assert_eq!(
+        vec![(1.0, 1), (2.0, 2), (3.0, 3)],
+        frequency_table(&[3.0, 2.0, 1.0, 3.0, 2.0, 3.0])
+    );
+}
+
+/// Shannon entropy, in bits, of the empirical distribution
+/// of input values: `-sum(p * log2(p))` over the
+/// proportion `p` of each distinct value. `NaN` values are
+/// ignored, as in `frequency_table`. The entropy of an
+/// empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, entropy(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), entropy(&[1.0, 2.0, 3.0, 4.0]));
+/// ```
+#[cfg(feature = "std")]
+pub fn entropy(nums: &[f64]) -> Option<f64> {
+    let table = frequency_table(nums);
+    let total: usize = table.iter().map(|&(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut bits = 0.0;
+    for (_, count) in table {
+        let p = count as f64 / total as f64;
+        bits -= p * p.log2();
+    }
+    Some(bits)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_entropy_uniform_two() {
+    assert_eq!(Some(1.0), entropy(&[0.0, 1.0, 1.0, 0.0]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_entropy_constant() {
+    assert_eq!(Some(0.0), entropy(&[5.0, 5.0, 5.0]));
+}
+
+/// Gini coefficient of input values, in `[0.0, 1.0]`: half
+/// the mean absolute difference between all pairs of
+/// values, relative to their mean. It is computed from the
+/// sorted values as
+/// `2 * sum(i * x_i) / (n * sum(x)) - (n + 1) / n`. The
+/// coefficient is undefined for an empty list, a list
+/// containing `NaN` or a negative value, or a list summing
+/// to zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, gini_coefficient(&[0.0, 0.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.5), gini_coefficient(&[0.0, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn gini_coefficient(nums: &[f64]) -> Option<f64> {
+    if nums.iter().any(|num| *num < 0.0) {
+        return None;
+    }
+    let nums = sorted(nums)?;
+    let total = kahan_sum(&nums);
+    if total == 0.0 {
+        return None;
+    }
+    let count = nums.len() as f64;
+    let mut weighted = 0.0;
+    for (i, val) in nums.iter().enumerate() {
+        weighted += (i + 1) as f64 * val;
+    }
+    Some(2.0 * weighted / (count * total) - (count + 1.0) / count)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_gini_coefficient_equal() {
+    assert!(gini_coefficient(&[4.0; 10]).unwrap().abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_gini_coefficient_unequal() {
+    let mut nums = vec![0.0; 999];
+    nums.push(100.0);
+    let g = gini_coefficient(&nums).unwrap();
+    assert!((g - 0.999).abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_gini_coefficient_negative() {
+    assert_eq!(None, gini_coefficient(&[1.0, -1.0, 2.0]));
+}
+
+/// Autocorrelation of input values at `lag`: the sum of
+/// products of deviations from the mean `lag` steps apart,
+/// relative to the sum of squared deviations. The
+/// autocorrelation is undefined for a `lag` not less than
+/// the length of the list, or a list with zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, autocorrelation(&[1.0, 2.0], 2));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-0.5), autocorrelation(&[1.0, -1.0], 1));
+/// ```
+pub fn autocorrelation(nums: &[f64], lag: usize) -> Option<f64> {
+    if lag >= nums.len() {
+        return None;
+    }
+    let xbar = mean(nums)?;
+    let mut denom = 0.0;
+    for val in nums {
+        denom += (val - xbar) * (val - xbar);
+    }
+    if denom == 0.0 {
+        return None;
+    }
+    let mut numer = 0.0;
+    for (x, y) in nums.iter().zip(&nums[lag..]) {
+        numer += (x - xbar) * (y - xbar);
+    }
+    Some(numer / denom)
+}
+
+#[test]
+fn test_autocorrelation_sine() {
+    let nums: Vec<f64> = (0..100)
+        .map(|i| (2.0 * std::f64::consts::PI * f64::from(i) / 20.0).sin())
+        .collect();
+    assert_eq!(Some(1.0), autocorrelation(&nums, 0));
+    assert!(autocorrelation(&nums, 10).unwrap() < -0.8);
+}
+
+#[test]
+fn test_autocorrelation_constant() {
+    assert_eq!(None, autocorrelation(&[2.0, 2.0, 2.0], 1));
+}
+
+/// Normalized cross-correlation of `xs` and `ys` at every
+/// lag: the sum of products of their deviations from their
+/// means, pairing `xs[i]` with `ys[i + lag]`, relative to
+/// the square root of the product of their sums of squared
+/// deviations. Element `k` of the result is for
+/// `lag = k - (xs.len() - 1)`, so lags run from
+/// `-(xs.len() - 1)` to `ys.len() - 1`, and a `ys` that is
+/// `xs` delayed by `d` steps peaks at `lag = d`. The
+/// cross-correlation is undefined for an empty list or a
+/// list with zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, cross_correlation(&[], &[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(
+///     Some(vec![0.5, -1.0, 0.5]),
+///     cross_correlation(&[1.0, -1.0], &[-1.0, 1.0])
+/// );
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cross_correlation(xs: &[f64], ys: &[f64]) -> Option<Vec<f64>> {
+    let xbar = mean(xs)?;
+    let ybar = mean(ys)?;
+    let denom = (sum_sq_dev(xs)? * sum_sq_dev(ys)?).sqrt();
+    if denom == 0.0 {
+        return None;
+    }
+    let xdev: Vec<f64> = xs.iter().map(|x| x - xbar).collect();
+    let ydev: Vec<f64> = ys.iter().map(|y| y - ybar).collect();
+    let mut result = vec![0.0; xs.len() + ys.len() - 1];
+    for (i, x) in xdev.iter().enumerate() {
+        for (j, y) in ydev.iter().enumerate() {
+            // The lag is j - i, offset to index from 0.
+            result[j + xs.len() - 1 - i] += x * y;
+        }
+    }
+    Some(result.into_iter().map(|sum| sum / denom).collect())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cross_correlation_identical() {
+    let nums: Vec<f64> = (0..30).map(|i| f64::from(i * i % 11)).collect();
+    let corr = cross_correlation(&nums, &nums).unwrap();
+    assert_eq!(2 * nums.len() - 1, corr.len());
+    let zero = nums.len() - 1;
+    assert!((corr[zero] - 1.0).abs() < 1e-12);
+    assert_eq!(Some(zero), argmax(&corr));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cross_correlation_shifted() {
+    let pulse = |center: i32| {
+        (0..40)
+            .map(|i| 1.0 / f64::from(1 + (i - center) * (i - center)))
+            .collect::<Vec<f64>>()
+    };
+    // The same pulse delayed by 3 steps.
+    let (xs, ys) = (pulse(10), pulse(13));
+    let corr = cross_correlation(&xs, &ys).unwrap();
+    assert_eq!(Some(xs.len() - 1 + 3), argmax(&corr));
+    assert_eq!(None, cross_correlation(&xs, &[2.0, 2.0]));
+}
+
+/// Elementwise differences `x - y` of two equal-length
+/// lists.
+#[cfg(feature = "alloc")]
+fn differences(xs: &[f64], ys: &[f64]) -> Option<Vec<f64>> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    Some(xs.iter().zip(ys).map(|(x, y)| x - y).collect())
+}
+
+/// Euclidean distance between two points given as lists of
+/// coordinates: the L2 norm of their difference. The
+/// distance is undefined for lists of differing lengths.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), distance(&[], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), distance(&[1.0], &[-1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn distance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    l2(&differences(xs, ys)?)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_distance_345() {
+    assert_eq!(Some(5.0), distance(&[0.0, 0.0], &[3.0, 4.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_distance_mismatched() {
+    assert_eq!(None, distance(&[0.0, 0.0], &[3.0]));
+}
+
+/// Manhattan distance between two points given as lists of
+/// coordinates: the L1 norm of their difference. The
+/// distance is undefined for lists of differing lengths.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, manhattan_distance(&[1.0], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(7.0), manhattan_distance(&[0.0, 0.0], &[3.0, -4.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn manhattan_distance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    l1(&differences(xs, ys)?)
+}
+
+/// Chebyshev distance between two points given as lists of
+/// coordinates: the largest absolute difference of any
+/// coordinate. The distance is undefined for lists of
+/// differing lengths.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, chebyshev_distance(&[1.0], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(4.0), chebyshev_distance(&[0.0, 0.0], &[3.0, -4.0]));
+/// ```
+#[cfg(feature = "std")]
+pub fn chebyshev_distance(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    lp_norm(&differences(xs, ys)?, f64::INFINITY)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_distances_compared() {
+    let xs = [1.0, -2.0, 3.0];
+    let ys = [4.0, 2.0, 3.0];
+    assert_eq!(Some(7.0), manhattan_distance(&xs, &ys));
+    assert_eq!(Some(5.0), distance(&xs, &ys));
+    assert_eq!(Some(4.0), chebyshev_distance(&xs, &ys));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_distances_mismatched() {
+    assert_eq!(None, manhattan_distance(&[1.0, 2.0], &[1.0]));
+    assert_eq!(None, chebyshev_distance(&[1.0, 2.0], &[1.0]));
+}
+
+/// Dot product of two equal-length lists. The dot product
+/// is undefined for lists of differing lengths.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, dot_product(&[1.0], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(11.0), dot_product(&[1.0, 2.0], &[3.0, 4.0]));
+/// ```
+pub fn dot_product(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    let mut sum = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        sum += x * y;
+    }
+    Some(sum)
+}
+
+#[test]
+fn test_dot_product_orthogonal() {
+    assert_eq!(Some(0.0), dot_product(&[1.0, 0.0], &[0.0, 5.0]));
+}
+
+/// Cosine similarity of two equal-length lists,
+/// `dot / (l2(xs) * l2(ys))`, in `[-1.0, 1.0]`. The
+/// similarity is undefined for lists of differing lengths
+/// or when either list has zero norm.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-1.0), cosine_similarity(&[3.0, 4.0], &[-6.0, -8.0]));
+/// ```
+pub fn cosine_similarity(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let dot = dot_product(xs, ys)?;
+    let norms = l2(xs)? * l2(ys)?;
+    if norms == 0.0 {
+        return None;
+    }
+    // Rounding can push parallel vectors just past 1.
+    Some((dot / norms).clamp(-1.0, 1.0))
+}
+
+#[test]
+fn test_cosine_similarity_identical() {
+    let xs = [75.5, 100.5, 95.5, 265.5, -37.0];
+    assert!((cosine_similarity(&xs, &xs).unwrap() - 1.0).abs() < 1e-12);
+    assert_eq!(Some(1.0), cosine_similarity(&[3.0, 4.0], &[3.0, 4.0]));
+}
+
+#[test]
+fn test_cosine_similarity_orthogonal() {
+    assert_eq!(Some(0.0), cosine_similarity(&[1.0, 1.0], &[-2.0, 2.0]));
+}
+
+#[test]
+fn test_cosine_similarity_mismatched() {
+    assert_eq!(None, cosine_similarity(&[1.0, 1.0], &[1.0]));
+}
+
+/// Input values scaled to unit L2 norm. The normalized
+/// vector is undefined for an empty list or a list of
+/// zeros.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, normalize(&[0.0, 0.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![-1.0]), normalize(&[-2.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn normalize(nums: &[f64]) -> Option<Vec<f64>> {
+    let norm = l2(nums)?;
+    if norm == 0.0 {
+        return None;
+    }
+    Some(nums.iter().map(|num| num / norm).collect())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_normalize_345() {
+    assert_eq!(Some(vec![0.6, 0.8]), normalize(&[3.0, 4.0]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_normalize_unit() {
+    let unit = normalize(&[75.5, 100.5, 95.5, 265.5, -37.0]).unwrap();
+    assert!((l2(&unit).unwrap() - 1.0).abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_normalize_empty() {
+    assert_eq!(None, normalize(&[]));
+}
+
+/// Softmax of input values, `exp(x) / sum(exp(x))`, a
+/// probability distribution favoring larger values. The
+/// maximum is subtracted before exponentiating so that
+/// large inputs do not overflow. As their limit, infinite
+/// values share the whole probability equally and leave
+/// none for the rest. The softmax of an empty list, or of
+/// one whose every value is negative infinity, is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, softmax(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![0.5, 0.5]), softmax(&[1e300, 1e300]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![1.0, 0.0]), softmax(&[f64::INFINITY, 1.0]));
+/// ```
+#[cfg(feature = "std")]
+pub fn softmax(nums: &[f64]) -> Option<Vec<f64>> {
+    let top = max(nums)?;
+    if top == f64::NEG_INFINITY {
+        return None;
+    }
+    // Subtracting an infinite maximum would give `NaN`.
+    if top == f64::INFINITY {
+        let share = 1.0 / nums.iter().filter(|&&num| num == top).count() as f64;
+        return Some(
+            nums.iter()
+                .map(|&num| if num == top { share } else { 0.0 })
+                .collect(),
+        );
+    }
+    let exps: Vec<f64> = nums.iter().map(|num| (num - top).exp()).collect();
+    let total = kahan_sum(&exps);
+    Some(exps.iter().map(|e| e / total).collect())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_softmax_distribution() {
+    let nums = [1.0, -2.0, 3.0, 0.5];
+    let probs = softmax(&nums).unwrap();
+    assert!((kahan_sum(&probs) - 1.0).abs() < 1e-12);
+    assert!(probs.iter().all(|&p| p > 0.0 && p < 1.0));
+    assert_eq!(max(&probs), Some(probs[2]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_softmax_infinite() {
+    assert_eq!(
+        Some(vec![0.5, 0.0, 0.5, 0.0]),
+        softmax(&[f64::INFINITY, 1.0, f64::INFINITY, f64::NEG_INFINITY])
+    );
+    assert_eq!(Some(vec![1.0, 0.0]), softmax(&[2.0, f64::NEG_INFINITY]));
+    assert_eq!(None, softmax(&[f64::NEG_INFINITY, f64::NEG_INFINITY]));
+}
+
+/// Reason a checked statistic is undefined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatError {
+    /// The input list was empty.
+    Empty,
+    /// The input list contained `NaN`.
+    ContainsNan,
+    /// The input had zero variance where a division by the
+    /// variance was needed.
+    ZeroVariance,
+    /// Paired input lists had differing lengths.
+    LengthMismatch,
+    /// A row of CSV input on the given line had no cell in
+    /// the requested column.
+    MissingColumn { line: u64 },
+    /// The cell on the given line of CSV input was not a
+    /// number.
+    NotNumeric { line: u64, column: usize },
+    /// CSV input could not be read or was malformed.
+    Unreadable,
+}
+
+impl core::fmt::Display for StatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let msg = match self {
+            StatError::Empty => "empty input",
+            StatError::ContainsNan => "input contains NaN",
+            StatError::ZeroVariance => "input has zero variance",
+            StatError::LengthMismatch => "inputs differ in length",
+            StatError::MissingColumn { line } => {
+                return write!(f, "line {} has no such column", line);
+            }
+            StatError::NotNumeric { line, column } => {
+                return write!(f, "line {}, column {} is not a number", line, column);
+            }
+            StatError::Unreadable => "input is not readable CSV",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StatError {}
+
+/// Check that input values are non-empty and free of `NaN`.
+fn check_nums(nums: &[f64]) -> Result<(), StatError> {
+    if nums.is_empty() {
+        return Err(StatError::Empty);
+    }
+    if nums.iter().any(|num| num.is_nan()) {
+        return Err(StatError::ContainsNan);
+    }
+    Ok(())
+}
+
+/// Arithmetic mean of input values, as for `mean`, with an
+/// error explaining why it is undefined. Unlike `mean`,
+/// input containing `NaN` is an error.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatError::Empty), mean_checked(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(1.0), mean_checked(&[-1.0, 3.0]));
+/// ```
+pub fn mean_checked(nums: &[f64]) -> Result<f64, StatError> {
+    check_nums(nums)?;
+    mean(nums).ok_or(StatError::Empty)
+}
+
+#[test]
+fn test_mean_checked_errors() {
+    assert_eq!(Err(StatError::Empty), mean_checked(&[]));
+    assert_eq!(Err(StatError::ContainsNan), mean_checked(&[1.0, f64::NAN]));
+}
+
+/// Population standard deviation of input values, as for
+/// `stddev`, with an error explaining why it is undefined.
+/// Unlike `stddev`, input containing `NaN` is an error.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatError::Empty), stddev_checked(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.0), stddev_checked(&[-1.0, 3.0]));
+/// ```
+pub fn stddev_checked(nums: &[f64]) -> Result<f64, StatError> {
+    check_nums(nums)?;
+    stddev(nums).ok_or(StatError::Empty)
+}
+
+#[test]
+fn test_stddev_checked_errors() {
+    assert_eq!(Err(StatError::Empty), stddev_checked(&[]));
+    assert_eq!(Err(StatError::ContainsNan), stddev_checked(&[f64::NAN]));
+}
+
+/// Median value of input values, as for `median`, with an
+/// error explaining why it is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatError::ContainsNan), median_checked(&[f64::NAN]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(-1.0), median_checked(&[-1.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn median_checked(nums: &[f64]) -> Result<f64, StatError> {
+    check_nums(nums)?;
+    median(nums).ok_or(StatError::Empty)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_checked_errors() {
+    assert_eq!(Err(StatError::Empty), median_checked(&[]));
+    assert_eq!(
+        Err(StatError::ContainsNan),
+        median_checked(&[1.0, f64::NAN])
+    );
+}
+
+/// Pearson correlation coefficient of paired input values,
+/// as for `pearson`, with an error explaining why it is
+/// undefined. Unlike `pearson`, input containing `NaN` is
+/// an error.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatError::ZeroVariance), pearson_checked(&[1.0, 2.0], &[3.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(-1.0), pearson_checked(&[1.0, 2.0], &[2.0, 1.0]));
+/// ```
+pub fn pearson_checked(xs: &[f64], ys: &[f64]) -> Result<f64, StatError> {
+    if xs.len() != ys.len() {
+        return Err(StatError::LengthMismatch);
+    }
+    check_nums(xs)?;
+    check_nums(ys)?;
+    pearson(xs, ys).ok_or(StatError::ZeroVariance)
+}
+
+#[test]
+fn test_pearson_checked_errors() {
+    assert_eq!(Err(StatError::LengthMismatch), pearson_checked(&[1.0], &[]));
+    assert_eq!(Err(StatError::Empty), pearson_checked(&[], &[]));
+    assert_eq!(
+        Err(StatError::ContainsNan),
+        pearson_checked(&[1.0, 2.0], &[f64::NAN, 1.0])
+    );
+    assert_eq!(
+        Err(StatError::ZeroVariance),
+        pearson_checked(&[1.0, 1.0], &[2.0, 1.0])
+    );
+}
+
+/// Percentile rank of `value` among input values: the
+/// proportion of values less than or equal to it, in
+/// `[0.0, 1.0]`. `NaN` values are never counted as less
+/// than or equal. The percentile rank is undefined for an
+/// empty list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, percentile_rank(&[], 1.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), percentile_rank(&[1.0, 2.0], 0.5));
+/// ```
+pub fn percentile_rank(nums: &[f64], value: f64) -> Option<f64> {
+    if nums.is_empty() {
+        return None;
+    }
+    let below = nums.iter().filter(|&&num| num <= value).count();
+    Some(below as f64 / nums.len() as f64)
+}
+
+#[test]
+fn test_percentile_rank_four() {
+    assert_eq!(Some(0.75), percentile_rank(&[1.0, 2.0, 3.0, 4.0], 3.0));
+    assert_eq!(Some(1.0), percentile_rank(&[1.0, 2.0, 3.0, 4.0], 4.0));
+}
+
+/// Accumulate input values in parallel into a
+/// `RunningStats`, merging the per-thread accumulators.
+#[cfg(feature = "rayon")]
+fn par_running_stats(nums: &[f64]) -> RunningStats {
+    use rayon::prelude::*;
+    nums.par_iter()
+        .fold(RunningStats::new, |mut stats, &num| {
+            stats.push(num);
+            stats
+        })
+        .reduce(RunningStats::new, |a, b| a.merge(&b))
+}
+
+/// Arithmetic mean of input values, as for `mean`, computed
+/// in parallel. Requires the `rayon` feature.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), par_mean(&[-1.0, 3.0]));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_mean(nums: &[f64]) -> Option<f64> {
+    par_running_stats(nums).mean()
+}
+
+/// Population standard deviation of input values, as for
+/// `stddev`, computed in parallel. Requires the `rayon`
+/// feature.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), par_stddev(&[-1.0, 3.0]));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_stddev(nums: &[f64]) -> Option<f64> {
+    par_running_stats(nums).stddev()
+}
+
+/// L2 norm of input values, as for `l2`, computed in
+/// parallel. Requires the `rayon` feature.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.0), par_l2(&[-3.0, 4.0]));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn par_l2(nums: &[f64]) -> Option<f64> {
+    use rayon::prelude::*;
+    // Each chunk keeps its own scaled sum of squares, as
+    // `l2` does, so that large values do not overflow.
+    let (scale, ssq) = nums
+        .par_iter()
+        .fold(
+            || (0.0, 1.0),
+            |acc, &num| combine_scaled(acc, (num.abs(), 1.0)),
+        )
+        .reduce(|| (0.0, 1.0), combine_scaled);
+    Some(scale * ssq.sqrt())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_matches_sequential() {
+    let nums: Vec<f64> = (0..1_000_000)
+        .map(|i| f64::from(i % 1000) * 0.37 - 100.0)
+        .collect();
+    let close = |a: Option<f64>, b: Option<f64>| (a.unwrap() - b.unwrap()).abs() < 1e-6;
+    assert!(close(mean(&nums), par_mean(&nums)));
+    assert!(close(stddev(&nums), par_stddev(&nums)));
+    assert!(close(l2(&nums), par_l2(&nums)));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_empty() {
+    assert_eq!(None, par_mean(&[]));
+    assert_eq!(None, par_stddev(&[]));
+    assert_eq!(Some(0.0), par_l2(&[]));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_l2_no_overflow() {
+    let nums = vec![1e200; 100_000];
+    let norm = par_l2(&nums).unwrap();
+    assert!((norm / (1e200 * 100_000f64.sqrt()) - 1.0).abs() < 1e-12);
+    assert!((par_l2(&[1e200, 1e200]).unwrap() - l2(&[1e200, 1e200]).unwrap()).abs() < 1e186);
+}
+
+/// Apply each statistic in `fns` to input values, giving
+/// the results in the same order. The statistics may be
+/// `StatFn`s, closures, or `BoxedStatFn`s to mix the two.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![Some(1.0), Some(2.0)], apply_all(&[-1.0, 3.0], &[mean, stddev]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let q = 0.5;
+/// assert_eq!(vec![Some(1.0)], apply_all(&[-1.0, 3.0], &[|xs: &[f64]| quantile(xs, q)]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn apply_all<F: Fn(&[f64]) -> Option<f64>>(nums: &[f64], fns: &[F]) -> Vec<Option<f64>> {
+    fns.iter().map(|stat| stat(nums)).collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_apply_all_matches() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    assert_eq!(
+        vec![mean(&nums), stddev(&nums), median(&nums), l2(&nums)],
+        apply_all(&nums, &[mean, stddev, median, l2])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_apply_all_empty() {
+    assert_eq!(vec![None, Some(0.0)], apply_all(&[], &[mean, l2]));
+    let none: &[StatFn] = &[];
+    assert!(apply_all(&[1.0], none).is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_apply_all_closures() {
+    let nums = [1.0, 2.0, 3.0, 4.0, 5.0];
+    let qs = [0.1, 0.9];
+    let fns: Vec<BoxedStatFn> = vec![
+        Box::new(mean),
+        Box::new(move |xs| quantile(xs, qs[0])),
+        Box::new(move |xs| quantile(xs, qs[1])),
+    ];
+    assert_eq!(
+        vec![Some(3.0), Some(1.4), Some(4.6)],
+        apply_all(&nums, &fns)
+    );
+}
+
+/// Means of the cosines and sines of `angles`, the mean
+/// resultant vector of their unit vectors. Undefined for
+/// an empty list.
+#[cfg(feature = "std")]
+fn mean_resultant(angles: &[f64]) -> Option<(f64, f64)> {
+    let cos = mean_iter(angles.iter().map(|angle| angle.cos()))?;
+    let sin = mean_iter(angles.iter().map(|angle| angle.sin()))?;
+    Some((cos, sin))
+}
+
+/// Circular mean of input angles in radians: the direction
+/// of the mean of their unit vectors, in `[0, 2π)`. The
+/// circular mean of an empty list, or of angles whose unit
+/// vectors cancel out, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, circular_mean(&[0.0, std::f64::consts::PI]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let east = circular_mean(&[0.25, 1.25]).unwrap();
+/// assert!((east - 0.75).abs() < 1e-12);
+/// ```
+#[cfg(feature = "std")]
+pub fn circular_mean(angles: &[f64]) -> Option<f64> {
+    use std::f64::consts::TAU;
+    let (cos, sin) = mean_resultant(angles)?;
+    // Cancelling unit vectors leave only rounding error
+    // behind, which has no meaningful direction.
+    if cos.hypot(sin) < f64::EPSILON {
+        return None;
+    }
+    let angle = sin.atan2(cos);
+    if angle >= 0.0 {
+        return Some(angle);
+    }
+    // A tiny negative angle can round up to a full turn.
+    let angle = angle + TAU;
+    Some(if angle < TAU { angle } else { 0.0 })
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_circular_mean_wraparound() {
+    use std::f64::consts::TAU;
+    let angle = circular_mean(&[0.1, TAU - 0.1]).unwrap();
+    assert!(angle < 1e-12 || TAU - angle < 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_circular_mean_range() {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    let angle = circular_mean(&[PI, PI + FRAC_PI_2]).unwrap();
+    assert!((angle - 1.25 * PI).abs() < 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_circular_mean_undefined() {
+    assert_eq!(None, circular_mean(&[]));
+    assert_eq!(None, circular_mean(&[1.0, 1.0 + std::f64::consts::PI]));
+}
+
+/// Circular standard deviation of input angles in radians,
+/// `sqrt(-2 ln R)` where `R` is the length of the mean of
+/// their unit vectors. It is 0.0 for identical angles and
+/// grows without bound as the unit vectors cancel out. The
+/// circular standard deviation of an empty list is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, circular_stddev(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), circular_stddev(&[1.0, 1.0]));
+/// ```
+#[cfg(feature = "std")]
+pub fn circular_stddev(angles: &[f64]) -> Option<f64> {
+    let (cos, sin) = mean_resultant(angles)?;
+    // Rounding can push the length of identical unit
+    // vectors just past 1.
+    let length = cos.hypot(sin).min(1.0);
+    Some((-2.0 * length.ln()).sqrt())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_circular_stddev_spread() {
+    use std::f64::consts::TAU;
+    let tight = circular_stddev(&[TAU - 0.05, 0.0, 0.05]).unwrap();
+    let wide = circular_stddev(&[0.0, 1.5, 3.0, 4.5]).unwrap();
+    assert!(tight < 0.05);
+    assert!(wide > 1.5);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_circular_stddev_small_angles() {
+    // For small spreads it approaches the linear stddev.
+    let angles = [-0.01, 0.0, 0.01];
+    let linear = stddev(&angles).unwrap();
+    assert!((circular_stddev(&angles).unwrap() - linear).abs() < 1e-6);
+}
+
+/// Pooled variance of `groups` of input values: the average
+/// of their sample variances weighted by their degrees of
+/// freedom `n - 1`, that is the total of their sums of
+/// squared deviations divided by `sum(n - 1)`. Empty groups
+/// are ignored. The pooled variance is undefined when there
+/// are fewer than two degrees of freedom in total.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, pooled_variance(&[vec![1.0, 2.0], vec![3.0]]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let groups = [vec![-1.0, 1.0], vec![0.0, 4.0]];
+/// assert_eq!(Some(5.0), pooled_variance(&groups));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn pooled_variance(groups: &[Vec<f64>]) -> Option<f64> {
+    let mut sum = 0.0;
+    let mut dof = 0;
+    for group in groups.iter().filter(|group| !group.is_empty()) {
+        sum += sum_sq_dev(group)?;
+        dof += group.len() - 1;
+    }
+    if dof < 2 {
+        return None;
+    }
+    Some(sum / dof as f64)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pooled_variance_two() {
+    let xs = vec![1.0, 2.0, 3.0, 4.0];
+    let ys = vec![-10.0, -20.0];
+    let expected = (3.0 * sample_variance(&xs).unwrap() + sample_variance(&ys).unwrap()) / 4.0;
+    let pooled = pooled_variance(&[xs, ys]).unwrap();
+    assert!((pooled - expected).abs() < 1e-12);
+    assert!((pooled - 55.0 / 4.0).abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pooled_variance_equal() {
+    let xs = vec![75.5, 100.5, 95.5, 265.5, -37.0];
+    let pooled = pooled_variance(&[xs.clone(), vec![], xs.clone()]);
+    assert!((pooled.unwrap() - sample_variance(&xs).unwrap()).abs() < 1e-9);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pooled_variance_undefined() {
+    assert_eq!(None, pooled_variance(&[]));
+    assert_eq!(None, pooled_variance(&[vec![1.0], vec![2.0]]));
+}
+
+/// One-sample t-statistic of input values against the
+/// hypothesized mean `mu0`, `(mean - mu0) / standard_error`.
+/// The t-statistic is undefined for lists with fewer than
+/// two elements or zero sample standard deviation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, t_statistic(&[1.0, 1.0], 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), t_statistic(&[1.0, 3.0], 0.0));
+/// ```
+pub fn t_statistic(nums: &[f64], mu0: f64) -> Option<f64> {
+    let error = standard_error(nums)?;
+    if error == 0.0 {
+        return None;
+    }
+    Some((mean(nums)? - mu0) / error)
+}
+
+#[test]
+fn test_t_statistic_null() {
+    assert_eq!(Some(0.0), t_statistic(&[1.0, 2.0, 3.0, 4.0, 5.0], 3.0));
+}
+
+#[test]
+fn test_t_statistic_shifted() {
+    let nums = [10.1, 9.8, 10.3, 9.9, 10.0, 10.2];
+    assert!(t_statistic(&nums, 5.0).unwrap() > 50.0);
+    assert!(t_statistic(&nums, 15.0).unwrap() < -50.0);
+}
+
+#[test]
+fn test_t_statistic_undefined() {
+    assert_eq!(None, t_statistic(&[1.0], 0.0));
+    assert_eq!(None, t_statistic(&[2.0, 2.0, 2.0], 0.0));
+}
+
+/// Welch's two-sample t-statistic comparing the means of
+/// `xs` and `ys`, with its Welch–Satterthwaite degrees of
+/// freedom, as `(t, df)`. The groups may differ in size and
+/// variance. The statistic is undefined when either group
+/// has fewer than two elements or both have zero sample
+/// variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, welch_t(&[1.0, 2.0], &[3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some((-2.5, 1.0)), welch_t(&[-2.0, 2.0], &[5.0, 5.0]));
+/// ```
+pub fn welch_t(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+    let (nx, ny) = (xs.len() as f64, ys.len() as f64);
+    let a = sample_variance(xs)? / nx;
+    let b = sample_variance(ys)? / ny;
+    if a + b == 0.0 {
+        return None;
+    }
+    let t = (mean(xs)? - mean(ys)?) / (a + b).sqrt();
+    let df = (a + b) * (a + b) / (a * a / (nx - 1.0) + b * b / (ny - 1.0));
+    Some((t, df))
+}
+
+#[test]
+fn test_welch_t_shifted() {
+    let xs = [10.1, 9.8, 10.3, 9.9, 10.0, 10.2];
+    let ys = [12.4, 11.9, 12.8, 12.1];
+    let (t, df) = welch_t(&xs, &ys).unwrap();
+    assert!(t < -10.0 && t > -20.0);
+    let (flipped, _) = welch_t(&ys, &xs).unwrap();
+    assert_eq!(-t, flipped);
+    // The degrees of freedom lie between the smaller group's
+    // and the pooled count.
+    assert!((3.0..=8.0).contains(&df));
+}
+
+#[test]
+fn test_welch_t_equal_sizes() {
+    // With equal sizes and variances the degrees of freedom
+    // are those of the pooled test.
+    let (_, df) = welch_t(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap();
+    assert!((df - 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_welch_t_undefined() {
+    assert_eq!(None, welch_t(&[1.0], &[1.0, 2.0]));
+    assert_eq!(None, welch_t(&[1.0, 1.0], &[2.0, 2.0]));
+}
+
+/// Complementary error function `1 - erf(x)`, to a relative
+/// accuracy of about `1e-12`: from the Maclaurin series of
+/// `erf` near zero and a continued fraction in the tails,
+/// where the series would lose precision.
+#[cfg(feature = "std")]
+fn erfc(x: f64) -> f64 {
+    use std::f64::consts::FRAC_2_SQRT_PI;
+    if x < 0.0 {
+        return 2.0 - erfc(-x);
+    }
+    if x < 2.0 {
+        let x2 = x * x;
+        let mut term = x;
+        let mut sum = x;
+        for n in 1.. {
+            term *= -x2 / f64::from(n);
+            let delta = term / f64::from(2 * n + 1);
+            sum += delta;
+            if delta.abs() <= 1e-17 * sum.abs() {
+                break;
+            }
+        }
+        return 1.0 - FRAC_2_SQRT_PI * sum;
+    }
+    let mut fraction = x;
+    for k in (1..=60).rev() {
+        fraction = x + f64::from(k) / 2.0 / fraction;
+    }
+    FRAC_2_SQRT_PI / 2.0 * (-x * x).exp() / fraction
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_erfc_known() {
+    // Reference values of erfc at 0.5, 1, 2 and 3.
+    let known = [
+        (0.5, 0.479_500_122_186_953_5),
+        (1.0, 0.157_299_207_050_285_13),
+        (2.0, 0.004_677_734_981_047_266),
+        (3.0, 2.209_049_699_858_544e-5),
+    ];
+    for &(x, expected) in &known {
+        assert!((erfc(x) - expected).abs() < 1e-12 * expected);
+        assert!((erfc(-x) - (2.0 - expected)).abs() < 1e-12);
+    }
+    assert_eq!(1.0, erfc(0.0));
+}
+
+/// Probability density at `x` of the normal distribution
+/// with the given `mean` and `stddev`. The density is
+/// undefined for a non-positive `stddev`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, normal_pdf(0.0, 0.0, 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// let peak = normal_pdf(0.0, 0.0, 1.0).unwrap();
+/// assert!((peak - 0.398_942_280_401_432_7).abs() < 1e-15);
+/// ```
+#[cfg(feature = "std")]
+pub fn normal_pdf(x: f64, mean: f64, stddev: f64) -> Option<f64> {
+    use std::f64::consts::{FRAC_1_SQRT_2, FRAC_2_SQRT_PI};
+    if stddev.is_nan() || stddev <= 0.0 {
+        return None;
+    }
+    let z = (x - mean) / stddev;
+    // 1 / sqrt(2 pi), from the constants std provides.
+    let scale = FRAC_2_SQRT_PI * FRAC_1_SQRT_2 / 2.0;
+    Some(scale / stddev * (-z * z / 2.0).exp())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_pdf_peak() {
+    let peak = normal_pdf(3.0, 3.0, 2.0).unwrap();
+    for &x in &[-1.0, 2.5, 2.9, 3.1, 3.5, 7.0] {
+        let density = normal_pdf(x, 3.0, 2.0).unwrap();
+        assert!(density < peak);
+        assert_eq!(Some(density), normal_pdf(6.0 - x, 3.0, 2.0));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_pdf_invalid() {
+    assert_eq!(None, normal_pdf(0.0, 0.0, -1.0));
+    assert_eq!(None, normal_pdf(0.0, 0.0, f64::NAN));
+}
+
+/// Cumulative probability at `x` of the normal distribution
+/// with the given `mean` and `stddev`: the probability of a
+/// value no greater than `x`. The probability is undefined
+/// for a non-positive `stddev`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, normal_cdf(0.0, 0.0, 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// let p = normal_cdf(1.96, 0.0, 1.0).unwrap();
+/// assert!((p - 0.975).abs() < 1e-4);
+/// ```
+#[cfg(feature = "std")]
+pub fn normal_cdf(x: f64, mean: f64, stddev: f64) -> Option<f64> {
+    use std::f64::consts::FRAC_1_SQRT_2;
+    if stddev.is_nan() || stddev <= 0.0 {
+        return None;
+    }
+    let z = (x - mean) / stddev;
+    Some(erfc(-z * FRAC_1_SQRT_2) / 2.0)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_cdf_mean() {
+    assert_eq!(Some(0.5), normal_cdf(0.0, 0.0, 1.0));
+    assert_eq!(Some(0.5), normal_cdf(-4.5, -4.5, 0.25));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_cdf_symmetric() {
+    for &x in &[0.1, 0.5, 1.0, 2.0, 3.0, 6.0] {
+        let lower = normal_cdf(-x, 0.0, 1.0).unwrap();
+        let upper = normal_cdf(x, 0.0, 1.0).unwrap();
+        assert!((lower + upper - 1.0).abs() < 1e-12);
+    }
+    // One standard deviation either side holds about 68.27%.
+    let within = normal_cdf(12.0, 10.0, 2.0).unwrap() - normal_cdf(8.0, 10.0, 2.0).unwrap();
+    assert!((within - 0.682_689_492_137_085_9).abs() < 1e-12);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_cdf_invalid() {
+    assert_eq!(None, normal_cdf(0.0, 0.0, -1.0));
+}
+
+/// Quantile of the normal distribution with the given
+/// `mean` and `stddev`: the value whose cumulative
+/// probability is `p`, inverting `normal_cdf`. Uses Peter
+/// Acklam's rational approximation, refined with one step
+/// of Halley's method. The quantile is undefined for `p`
+/// outside `(0.0, 1.0)` or a non-positive `stddev`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, normal_ppf(1.0, 0.0, 1.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// let z = normal_ppf(0.975, 0.0, 1.0).unwrap();
+/// assert!((z - 1.96).abs() < 1e-3);
+/// ```
+#[cfg(feature = "std")]
+pub fn normal_ppf(p: f64, mean: f64, stddev: f64) -> Option<f64> {
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+    if !(p > 0.0 && p < 1.0) || stddev.is_nan() || stddev <= 0.0 {
+        return None;
+    }
+    // Rational approximations for the central region and
+    // for the two tails, which mirror each other.
+    let tail = |q: f64| {
+        let q = (-2.0 * q.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    let z = if p < P_LOW {
+        tail(p)
+    } else if p > 1.0 - P_LOW {
+        -tail(1.0 - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    };
+    // The approximation is good to about 1e-9; one Halley
+    // step brings it to full precision.
+    let error = erfc(-z * FRAC_1_SQRT_2) / 2.0 - p;
+    let u = error * (2.0 * PI).sqrt() * (z * z / 2.0).exp();
+    let z = z - u / (1.0 + z * u / 2.0);
+    Some(mean + stddev * z)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_ppf_known() {
+    assert!(normal_ppf(0.5, 0.0, 1.0).unwrap().abs() < 1e-15);
+    let z = normal_ppf(0.975, 0.0, 1.0).unwrap();
+    assert!((z - 1.959_963_984_540_054).abs() < 1e-12);
+    let z = normal_ppf(0.001, 0.0, 1.0).unwrap();
+    assert!((z + 3.090_232_306_167_813_5).abs() < 1e-12);
+    assert_eq!(Some(12.0), normal_ppf(0.5, 12.0, 3.0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_ppf_inverts_cdf() {
+    for &p in &[1e-10, 0.01, 0.02425, 0.2, 0.6, 0.9, 0.99, 1.0 - 1e-10] {
+        let x = normal_ppf(p, 5.0, 2.0).unwrap();
+        let back = normal_cdf(x, 5.0, 2.0).unwrap();
+        assert!((back - p).abs() < 1e-12 * p.max(1e-3));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_normal_ppf_invalid() {
+    assert_eq!(None, normal_ppf(0.0, 0.0, 1.0));
+    assert_eq!(None, normal_ppf(f64::NAN, 0.0, 1.0));
+    assert_eq!(None, normal_ppf(0.5, 0.0, 0.0));
+}
+
+/// Two-sided confidence interval for the mean of input
+/// values at the given `confidence` level, as `(lower,
+/// upper)`: `mean ± z * standard_error`, with `z` the
+/// normal quantile at `(1 + confidence) / 2`, so that 0.95
+/// gives `z ≈ 1.96`. This normal approximation suits larger
+/// samples. The interval is undefined for lists with fewer
+/// than two elements or `confidence` outside `(0.0, 1.0)`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, confidence_interval(&[1.0], 0.95));
+/// ```
+/// ```
+/// # use stats::*;
+/// let (lower, upper) = confidence_interval(&[1.0, 3.0], 0.95).unwrap();
+/// assert!((lower - 0.04).abs() < 1e-2 && (upper - 3.96).abs() < 1e-2);
+/// ```
+#[cfg(feature = "std")]
+pub fn confidence_interval(nums: &[f64], confidence: f64) -> Option<(f64, f64)> {
+    if !(confidence > 0.0 && confidence < 1.0) {
+        return None;
+    }
+    let z = normal_ppf((1.0 + confidence) / 2.0, 0.0, 1.0)?;
+    let xbar = mean(nums)?;
+    let half_width = z * standard_error(nums)?;
+    Some((xbar - half_width, xbar + half_width))
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_confidence_interval_known() {
+    // The mean is 5 and the standard error sqrt(32 / 7 / 8).
+    let nums = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let (lower, upper) = confidence_interval(&nums, 0.95).unwrap();
+    assert!((lower - 3.518_406_490_932_507).abs() < 1e-9);
+    assert!((upper - 6.481_593_509_067_493).abs() < 1e-9);
+    let (narrow_lower, narrow_upper) = confidence_interval(&nums, 0.5).unwrap();
+    assert!(lower < narrow_lower && narrow_upper < upper);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_confidence_interval_invalid() {
+    assert_eq!(None, confidence_interval(&[], 0.95));
+    assert_eq!(None, confidence_interval(&[1.0, 2.0], 0.0));
+    assert_eq!(None, confidence_interval(&[1.0, 2.0], 1.0));
+}
+
+/// Median of the combined values of `a` and `b`, which must
+/// already each be sorted in ascending order, without
+/// merging them: a binary search over the shorter list
+/// takes `O(log(min(m, n)))` steps. For an even combined
+/// count the two middle values are averaged, as for
+/// `median_averaged`. The result is unspecified, and may be
+/// `None`, for unsorted lists or lists containing `NaN`. The
+/// median of two empty lists is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median_of_two_sorted(&[], &[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.5), median_of_two_sorted(&[1.0, 3.0], &[2.0, 4.0]));
+/// ```
+pub fn median_of_two_sorted(a: &[f64], b: &[f64]) -> Option<f64> {
+    if a.len() > b.len() {
+        return median_of_two_sorted(b, a);
+    }
+    let (m, n) = (a.len(), b.len());
+    if n == 0 {
+        return None;
+    }
+    // Search for the split of `a` at `i` and `b` at `j`
+    // whose left parts together hold the lower half.
+    let half = (m + n).div_ceil(2);
+    let (mut lo, mut hi) = (0, m);
+    while lo <= hi {
+        let i = (lo + hi) / 2;
+        let j = half - i;
+        let a_left = if i == 0 { f64::NEG_INFINITY } else { a[i - 1] };
+        let a_right = if i == m { f64::INFINITY } else { a[i] };
+        let b_left = if j == 0 { f64::NEG_INFINITY } else { b[j - 1] };
+        let b_right = if j == n { f64::INFINITY } else { b[j] };
+        if a_left > b_right {
+            hi = i.checked_sub(1)?;
+        } else if b_left > a_right {
+            lo = i + 1;
+        } else {
+            let left = a_left.max(b_left);
+            if (m + n) % 2 == 1 {
+                return Some(left);
+            }
+            return Some((left + a_right.min(b_right)) / 2.0);
+        }
+    }
+    None
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_median_of_two_sorted_unequal() {
+    let a = [-37.0, 75.5, 95.5];
+    let b = [1.0, 2.0, 100.5, 265.5, 300.0, 301.0];
+    let mut all = [&a[..], &b[..]].concat();
+    assert_eq!(median(&all), median_of_two_sorted(&a, &b));
+    assert_eq!(median(&all), median_of_two_sorted(&b, &a));
+    all.push(400.0);
+    let b = &all[a.len()..];
+    assert_eq!(median_averaged(&all), median_of_two_sorted(&a, b));
+}
+
+#[test]
+fn test_median_of_two_sorted_one_empty() {
+    assert_eq!(Some(2.0), median_of_two_sorted(&[], &[1.0, 2.0, 3.0]));
+    assert_eq!(Some(2.5), median_of_two_sorted(&[1.0, 2.0, 3.0, 4.0], &[]));
+    assert_eq!(Some(5.0), median_of_two_sorted(&[5.0], &[]));
+}
+
+#[test]
+fn test_median_of_two_sorted_disjoint() {
+    assert_eq!(
+        Some(3.5),
+        median_of_two_sorted(&[5.0, 6.0], &[1.0, 2.0, 3.0, 4.0])
+    );
+    assert_eq!(
+        Some(3.0),
+        median_of_two_sorted(&[1.0, 2.0], &[3.0, 4.0, 5.0])
+    );
+}
+
+#[test]
+fn test_median_of_two_sorted_unsorted() {
+    // Unspecified result, but the search must terminate.
+    let _ = median_of_two_sorted(&[9.0, 1.0, 5.0], &[8.0, 0.0, 3.0, 2.0]);
+    let _ = median_of_two_sorted(&[f64::NAN, 1.0], &[2.0, f64::NAN, 0.0]);
+    let _ = median_of_two_sorted(&[3.0, 2.0, 1.0], &[6.0, 5.0, 4.0]);
+}
+
+/// Values of the numeric `column` (counting from 0) of the
+/// CSV read from `reader`, skipping its header row. Cells
+/// may have surrounding spaces.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let data = "name,score\nann,3.5\nbo,1.5\n";
+/// let scores = read_column(data.as_bytes(), 1).unwrap();
+/// assert_eq!(Some(2.5), mean(&scores));
+/// ```
+/// ```
+/// # use stats::*;
+/// let data = "name,score\nann,3.5\nbo,n/a\n";
+/// let error = read_column(data.as_bytes(), 1).unwrap_err();
+/// assert_eq!("line 3, column 1 is not a number", error.to_string());
+/// ```
+#[cfg(feature = "csv")]
+pub fn read_column<R: std::io::Read>(reader: R, column: usize) -> Result<Vec<f64>, StatError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let mut values = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|_| StatError::Unreadable)?;
+        let line = record.position().map_or(0, |pos| pos.line());
+        let cell = record
+            .get(column)
+            .ok_or(StatError::MissingColumn { line })?;
+        let value = cell
+            .parse()
+            .map_err(|_| StatError::NotNumeric { line, column })?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_read_column_mean() {
+    let data = "x, y\n1, 10\n2, 20.5\n3, -4e1\n";
+    assert_eq!(Ok(vec![1.0, 2.0, 3.0]), read_column(data.as_bytes(), 0));
+    let ys = read_column(data.as_bytes(), 1).unwrap();
+    assert_eq!(Some(-9.5 / 3.0), mean(&ys));
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_read_column_errors() {
+    assert_eq!(Ok(vec![]), read_column("x\n".as_bytes(), 0));
+    assert_eq!(
+        Err(StatError::MissingColumn { line: 3 }),
+        read_column("x,y\n1,2\n3\n".as_bytes(), 1)
+    );
+    assert_eq!(
+        Err(StatError::NotNumeric { line: 2, column: 0 }),
+        read_column("x\n\"\"\n".as_bytes(), 0)
+    );
+    assert_eq!(
+        Err(StatError::Unreadable),
+        read_column(&[b'x', b'\n', 0xff, b'\n'][..], 0)
+    );
+}
+
+/// Small seeded pseudo-random number generator (SplitMix64),
+/// so that sampling is reproducible without a dependency.
+/// It is not suitable for cryptography.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform index in `0..n`, for `n > 0`, by Lemire's
+    /// multiply-and-reject method.
+    fn below(&mut self, n: usize) -> usize {
+        let n = n as u64;
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(n);
+            if product as u64 >= threshold {
+                return (product >> 64) as usize;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rng_below_uniform() {
+    let mut rng = Rng::new(7);
+    let mut counts = [0usize; 6];
+    for _ in 0..60_000 {
+        counts[rng.below(6)] += 1;
+    }
+    assert!(counts.iter().all(|&count| (9_500..10_500).contains(&count)));
+}
+
+/// Uniform random sample of at most `k` of the values of
+/// `iter`, using reservoir sampling (Algorithm R) so that
+/// the values need not all be stored. The same `seed` gives
+/// the same sample. Streams of at most `k` values are
+/// returned whole.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![1.0, 2.0], reservoir_sample(vec![1.0, 2.0], 3, 42));
+/// ```
+/// ```
+/// # use stats::*;
+/// let sample = reservoir_sample((0..1000).map(f64::from), 10, 42);
+/// assert_eq!(10, sample.len());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn reservoir_sample<I: IntoIterator<Item = f64>>(iter: I, k: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Rng::new(seed);
+    let mut reservoir = Vec::with_capacity(k);
+    for (i, val) in iter.into_iter().enumerate() {
+        if i < k {
+            reservoir.push(val);
+        } else {
+            // Keep the i-th value with probability k / (i + 1).
+            let j = rng.below(i + 1);
+            if j < k {
+                reservoir[j] = val;
+            }
+        }
+    }
+    reservoir
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_reservoir_sample_deterministic() {
+    let stream = || (0..10_000).map(f64::from);
+    let sample = reservoir_sample(stream(), 5, 1234);
+    assert_eq!(sample, reservoir_sample(stream(), 5, 1234));
+    assert_ne!(sample, reservoir_sample(stream(), 5, 4321));
+    assert_eq!(5, sample.len());
+    assert!(sample.iter().all(|val| (0.0..10_000.0).contains(val)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_reservoir_sample_short() {
+    assert_eq!(
+        vec![1.0, 2.0, 3.0],
+        reservoir_sample(vec![1.0, 2.0, 3.0], 5, 99)
+    );
+    assert!(reservoir_sample(vec![1.0, 2.0, 3.0], 0, 99).is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_reservoir_sample_uniform() {
+    // Each of 10 values should be kept about 3 times in 10.
+    let mut counts = [0usize; 10];
+    for seed in 0..10_000 {
+        for val in reservoir_sample((0..10).map(f64::from), 3, seed) {
+            counts[val as usize] += 1;
+        }
+    }
+    assert!(counts.iter().all(|&count| (2_800..3_200).contains(&count)));
+}
+
+/// Bootstrap percentile confidence interval for the
+/// statistic `stat` of input values at the given
+/// `confidence` level, as `(lower, upper)`. The values are
+/// resampled with replacement `iterations` times, and the
+/// bounds are the quantiles at `(1 - confidence) / 2` and
+/// `(1 + confidence) / 2` of the statistic over the
+/// resamples, skipping resamples where it is undefined or
+/// `NaN`. The same `seed` gives the same interval. The
+/// interval is undefined for an empty list, no iterations,
+/// `confidence` outside `(0.0, 1.0)`, or when no resample
+/// gives a statistic.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, bootstrap_ci(&[], mean, 100, 0.95, 42));
+/// ```
+/// ```
+/// # use stats::*;
+/// let (lower, upper) = bootstrap_ci(&[1.0, 2.0, 3.0, 4.0], mean, 100, 0.9, 42).unwrap();
+/// assert!(1.0 <= lower && lower <= upper && upper <= 4.0);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn bootstrap_ci(
+    nums: &[f64],
+    stat: StatFn,
+    iterations: usize,
+    confidence: f64,
+    seed: u64,
+) -> Option<(f64, f64)> {
+    if nums.is_empty() || iterations == 0 || !(confidence > 0.0 && confidence < 1.0) {
+        return None;
+    }
+    let mut rng = Rng::new(seed);
+    let mut resample = vec![0.0; nums.len()];
+    let mut estimates = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        for val in resample.iter_mut() {
+            *val = nums[rng.below(nums.len())];
+        }
+        if let Some(estimate) = stat(&resample).filter(|estimate| !estimate.is_nan()) {
+            estimates.push(estimate);
+        }
+    }
+    let estimates = sorted(&estimates)?;
+    let lower = sorted_quantile(&estimates, (1.0 - confidence) / 2.0)?;
+    let upper = sorted_quantile(&estimates, (1.0 + confidence) / 2.0)?;
+    Some((lower, upper))
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_bootstrap_ci_brackets_mean() {
+    let nums: Vec<f64> = (0..200).map(|i| f64::from(i % 20) * 0.5 + 3.0).collect();
+    let xbar = mean(&nums).unwrap();
+    let (lower, upper) = bootstrap_ci(&nums, mean, 2000, 0.95, 2024).unwrap();
+    assert!(lower < xbar && xbar < upper);
+    // The interval is about 2 * 1.96 standard errors wide.
+    let width = 2.0 * 1.96 * standard_error(&nums).unwrap();
+    assert!(((upper - lower) - width).abs() < 0.15 * width);
+    assert_eq!(
+        Some((lower, upper)),
+        bootstrap_ci(&nums, mean, 2000, 0.95, 2024)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_bootstrap_ci_invalid() {
+    assert_eq!(None, bootstrap_ci(&[1.0, 2.0], mean, 0, 0.95, 1));
+    assert_eq!(None, bootstrap_ci(&[1.0, 2.0], mean, 10, 1.0, 1));
+    assert_eq!(None, bootstrap_ci(&[1.0, 2.0], mean, 10, f64::NAN, 1));
+    // A single value never gives a sample standard deviation.
+    assert_eq!(None, bootstrap_ci(&[1.0], sample_stddev, 10, 0.95, 1));
+}
+
+/// Streaming sketch of a distribution for approximate
+/// quantiles, using Dunning's t-digest. Values are grouped
+/// into weighted centroids whose size is limited to
+/// `4 * n * q * (1 - q) / compression` at quantile `q`, so
+/// centroids near the tails stay small and tail quantiles
+/// are more accurate than central ones. Larger
+/// `compression` keeps more centroids for more accuracy.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut digest = TDigest::new(100.0);
+/// for x in &[4.0, 1.0, 3.0, 2.0] {
+///     digest.push(*x);
+/// }
+/// assert_eq!(Some(2.5), digest.quantile(0.5));
+/// assert_eq!(Some(4.0), digest.quantile(1.0));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TDigest {
+    compression: f64,
+    /// Centroids as `(mean, weight)`, sorted by mean.
+    centroids: Vec<(f64, f64)>,
+    /// Weighted values not yet merged into the centroids.
+    buffer: Vec<(f64, f64)>,
+    count: f64,
+    min: f64,
+    max: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl TDigest {
+    /// Make a new empty digest with the given `compression`.
+    /// A `compression` below 1.0, or `NaN`, is taken as 1.0.
+    pub fn new(compression: f64) -> Self {
+        let compression = if compression >= 1.0 { compression } else { 1.0 };
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Accumulate the value `x`. Non-finite values, `NaN` or
+    /// infinite, are skipped.
+    pub fn push(&mut self, x: f64) {
+        self.add(x, 1.0);
+    }
+
+    /// Accumulate all the values of `other` into this
+    /// digest.
+    pub fn merge(&mut self, other: &TDigest) {
+        for &(mean, weight) in other.centroids.iter().chain(&other.buffer) {
+            self.add(mean, weight);
+        }
+    }
+
+    /// Approximate quantile `q` of the accumulated values,
+    /// for `q` in `[0.0, 1.0]`, interpolating between the
+    /// centroids and out to the exact minimum and maximum.
+    /// The quantile is undefined for an empty digest or a
+    /// `q` outside `[0.0, 1.0]`.
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.count == 0.0 || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        let merged;
+        let centroids = if self.buffer.is_empty() {
+            &self.centroids
+        } else {
+            merged = self.merged();
+            &merged
+        };
+        // Each centroid's mean is placed at the middle of its
+        // weight, with the minimum and maximum at either end.
+        let target = q * self.count;
+        let mut before = 0.0;
+        let mut prev = (self.min, 0.0);
+        for &(mean, weight) in centroids {
+            let center = before + weight / 2.0;
+            if target <= center {
+                return Some(interpolate(prev, (mean, center), target));
+            }
+            prev = (mean, center);
+            before += weight;
+        }
+        Some(interpolate(prev, (self.max, self.count), target))
+    }
+
+    fn add(&mut self, mean: f64, weight: f64) {
+        // Merging two infinite centroids would give a `NaN`
+        // mean.
+        if !mean.is_finite() {
+            return;
+        }
+        self.buffer.push((mean, weight));
+        self.count += weight;
+        self.min = self.min.min(mean);
+        self.max = self.max.max(mean);
+        // The cast saturates, and so must the product, for a
+        // huge compression.
+        if self.buffer.len() >= (self.compression as usize).saturating_mul(5) {
+            self.centroids = self.merged();
+            self.buffer.clear();
+        }
+    }
+
+    /// Centroids with the buffered values merged in: adjacent
+    /// centroids are combined, in order, while the result
+    /// stays within the size limit.
+    fn merged(&self) -> Vec<(f64, f64)> {
+        let mut points: Vec<(f64, f64)> =
+            self.centroids.iter().chain(&self.buffer).copied().collect();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut merged = Vec::new();
+        let mut points = points.into_iter();
+        let mut current = match points.next() {
+            Some(point) => point,
+            None => return merged,
+        };
+        let mut before = 0.0;
+        for (mean, weight) in points {
+            let combined = current.1 + weight;
+            let q = (before + combined / 2.0) / self.count;
+            if combined <= 4.0 * self.count * q * (1.0 - q) / self.compression {
+                current.0 += (mean - current.0) * weight / combined;
+                current.1 = combined;
+            } else {
+                before += current.1;
+                merged.push(current);
+                current = (mean, weight);
+            }
+        }
+        merged.push(current);
+        merged
+    }
+}
+
+/// Value at `position` on the line through the points
+/// `(value, position)` `a` and `b`.
+#[cfg(feature = "alloc")]
+fn interpolate(a: (f64, f64), b: (f64, f64), position: f64) -> f64 {
+    if b.1 <= a.1 {
+        return b.0;
+    }
+    a.0 + (b.0 - a.0) * (position - a.1) / (b.1 - a.1)
+}
+
+/// A million values spread evenly over `[0, 1)`, in a
+/// scrambled order.
+#[cfg(all(feature = "alloc", test))]
+fn scrambled_uniform() -> impl Iterator<Item = f64> {
+    (0..1_000_000u64).map(|i| (i * 611_953 % 1_000_000) as f64 / 1e6)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_t_digest_million() {
+    let mut digest = TDigest::new(100.0);
+    for x in scrambled_uniform() {
+        digest.push(x);
+    }
+    let median = digest.quantile(0.5).unwrap();
+    let p99 = digest.quantile(0.99).unwrap();
+    let p999 = digest.quantile(0.999).unwrap();
+    assert!((median - 0.5).abs() < 5e-3);
+    assert!((p99 - 0.99).abs() < 1e-3);
+    assert!((p999 - 0.999).abs() < 1e-4);
+    assert_eq!(Some(0.0), digest.quantile(0.0));
+    assert_eq!(Some(0.999_999), digest.quantile(1.0));
+    assert!(digest.centroids.len() < 1000);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_t_digest_merge() {
+    let mut a = TDigest::new(100.0);
+    let mut b = TDigest::new(100.0);
+    for (i, x) in scrambled_uniform().take(100_000).enumerate() {
+        if i % 3 == 0 {
+            a.push(x);
+        } else {
+            b.push(2.0 * x);
+        }
+    }
+    let mut both = a.clone();
+    both.merge(&b);
+    assert_eq!(a.count + b.count, both.count);
+    // A third of the values lie in [0, 1) and two thirds in
+    // [0, 2), so half lie below 0.75.
+    assert!((both.quantile(0.5).unwrap() - 0.75).abs() < 1e-2);
+    assert_eq!(Some(0.0), both.quantile(0.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_t_digest_empty() {
+    let mut digest = TDigest::new(0.0);
+    assert_eq!(None, digest.quantile(0.5));
+    digest.push(f64::NAN);
+    assert_eq!(None, digest.quantile(0.5));
+    digest.push(7.0);
+    assert_eq!(Some(7.0), digest.quantile(0.5));
+    assert_eq!(None, digest.quantile(1.5));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_t_digest_infinite() {
+    let mut digest = TDigest::new(10.0);
+    for _ in 0..200 {
+        digest.push(f64::INFINITY);
+        digest.push(f64::NEG_INFINITY);
+    }
+    assert_eq!(None, digest.quantile(0.5));
+    for x in 0..200 {
+        digest.push(f64::INFINITY);
+        digest.push(f64::from(x));
+    }
+    assert_eq!(200.0, digest.count);
+    assert_eq!(Some(0.0), digest.quantile(0.0));
+    assert_eq!(Some(199.0), digest.quantile(1.0));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_t_digest_huge_compression() {
+    for &compression in &[f64::INFINITY, 1e300, usize::MAX as f64 / 4.0] {
+        let mut digest = TDigest::new(compression);
+        for x in &[3.0, 1.0, 2.0] {
+            digest.push(*x);
+        }
+        assert_eq!(Some(2.0), digest.quantile(0.5));
+    }
+}
+
+/// Shuffle input values in place into a uniformly random
+/// order, by the Fisher–Yates algorithm. The same `seed`
+/// gives the same order.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut nums = [1.0, 2.0, 3.0, 4.0];
+/// shuffle(&mut nums, 42);
+/// assert_eq!(10.0, sum_iter(nums.iter().copied()));
+/// ```
+pub fn shuffle(nums: &mut [f64], seed: u64) {
+    let mut rng = Rng::new(seed);
+    for i in (1..nums.len()).rev() {
+        nums.swap(i, rng.below(i + 1));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_shuffle_deterministic() {
+    let nums: Vec<f64> = (0..50).map(f64::from).collect();
+    let mut a = nums.clone();
+    let mut b = nums.clone();
+    shuffle(&mut a, 7);
+    shuffle(&mut b, 7);
+    assert_eq!(a, b);
+    assert_ne!(nums, a);
+    shuffle(&mut b, 8);
+    assert_ne!(a, b);
+    assert_eq!(Some(nums), sorted(&a));
+}
+
+#[test]
+fn test_shuffle_uniform() {
+    // Each of the 6 orders of 3 values should be about equally
+    // common, identified by where the first two values land.
+    let mut counts = [0usize; 9];
+    for seed in 0..60_000 {
+        let mut nums = [0.0, 1.0, 2.0];
+        shuffle(&mut nums, seed);
+        counts[3 * nums[0] as usize + nums[1] as usize] += 1;
+    }
+    for (i, &count) in counts.iter().enumerate() {
+        if i / 3 == i % 3 {
+            assert_eq!(0, count);
+        } else {
+            assert!((9_500..10_500).contains(&count));
+        }
+    }
+    let mut empty: [f64; 0] = [];
+    shuffle(&mut empty, 0);
+}
+
+/// Uniform random sample of `k` of the input values drawn
+/// without replacement, by a partial Fisher–Yates shuffle
+/// of a copy. The same `seed` gives the same sample. The
+/// sample is undefined for a `k` larger than the list.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample(&[1.0, 2.0], 3, 42));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(3), sample(&[1.0, 2.0, 3.0, 4.0], 3, 42).map(|s| s.len()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sample(nums: &[f64], k: usize, seed: u64) -> Option<Vec<f64>> {
+    if k > nums.len() {
+        return None;
+    }
+    let mut rng = Rng::new(seed);
+    let mut pool = nums.to_vec();
+    for i in 0..k {
+        let j = i + rng.below(pool.len() - i);
+        pool.swap(i, j);
+    }
+    pool.truncate(k);
+    Some(pool)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sample_from_input() {
+    let nums: Vec<f64> = (0..100).map(|i| f64::from(i) * 1.5).collect();
+    let drawn = sample(&nums, 10, 99).unwrap();
+    assert_eq!(10, drawn.len());
+    assert!(drawn.iter().all(|val| nums.contains(val)));
+    // The values are distinct, since none are repeated.
+    let mut distinct = sorted(&drawn).unwrap();
+    distinct.dedup();
+    assert_eq!(10, distinct.len());
+    assert_eq!(Some(drawn), sample(&nums, 10, 99));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sample_edges() {
+    assert_eq!(Some(vec![]), sample(&[], 0, 1));
+    assert_eq!(None, sample(&[], 1, 1));
+    let all = sample(&[3.0, 1.0, 2.0], 3, 1).unwrap();
+    assert_eq!(Some(vec![1.0, 2.0, 3.0]), sorted(&all));
+}
+
+/// Mann–Whitney U statistic of `xs` against `ys`: the
+/// number of pairs in which the value from `xs` is the
+/// larger, counting ties as half. It is computed from the
+/// ranks of the pooled values, as `R - n(n + 1) / 2` with `R`
+/// the rank sum of the `n` values of `xs`, so ranges from 0
+/// to `xs.len() * ys.len()`. The statistic is undefined if
+/// either list is empty or contains `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, mann_whitney_u(&[], &[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.5), mann_whitney_u(&[1.0, 3.0], &[2.0, 3.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn mann_whitney_u(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.is_empty() || ys.is_empty() {
+        return None;
+    }
+    let pooled = [xs, ys].concat();
+    if pooled.iter().any(|val| val.is_nan()) {
+        return None;
+    }
+    let rank_sum: f64 = rank(&pooled)[..xs.len()].iter().sum();
+    let n = xs.len() as f64;
+    Some(rank_sum - n * (n + 1.0) / 2.0)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mann_whitney_u_separated() {
+    let low = [1.0, 2.5, 3.0, 0.5];
+    let high = [10.0, 12.0, 11.5];
+    assert_eq!(Some(0.0), mann_whitney_u(&low, &high));
+    assert_eq!(Some(12.0), mann_whitney_u(&high, &low));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_mann_whitney_u_complementary() {
+    let xs = [1.0, 4.0, 4.0, 7.0, 2.0];
+    let ys = [4.0, 3.0, 9.0];
+    let u = mann_whitney_u(&xs, &ys).unwrap();
+    assert_eq!(Some(15.0 - u), mann_whitney_u(&ys, &xs));
+    assert_eq!(None, mann_whitney_u(&xs, &[f64::NAN]));
+}
+
+/// Pearson's chi-squared statistic of `observed` counts
+/// against `expected` counts, `sum((o - e)^2 / e)`. It is
+/// 0.0 when they agree and grows as they diverge. The
+/// statistic is undefined for empty lists, lists of
+/// differing lengths, or any expected count that is not
+/// positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, chi_squared(&[1.0], &[0.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), chi_squared(&[2.0, 6.0], &[4.0, 4.0]));
+/// ```
+pub fn chi_squared(observed: &[f64], expected: &[f64]) -> Option<f64> {
+    if observed.is_empty() || observed.len() != expected.len() {
+        return None;
+    }
+    if expected.iter().any(|e| e.is_nan() || *e <= 0.0) {
+        return None;
+    }
+    let mut sum = 0.0;
+    for (o, e) in observed.iter().zip(expected) {
+        sum += (o - e) * (o - e) / e;
+    }
+    Some(sum)
+}
+
+#[test]
+fn test_chi_squared_agreement() {
+    let counts = [12.0, 30.5, 8.0, 49.5];
+    assert_eq!(Some(0.0), chi_squared(&counts, &counts));
+}
+
+#[test]
+fn test_chi_squared_divergent() {
+    // A die rolled 60 times, against 10 of each face.
+    let observed = [5.0, 8.0, 9.0, 8.0, 10.0, 20.0];
+    let chi = chi_squared(&observed, &[10.0; 6]).unwrap();
+    assert!((chi - 13.4).abs() < 1e-12);
+}
+
+#[test]
+fn test_chi_squared_invalid() {
+    assert_eq!(None, chi_squared(&[], &[]));
+    assert_eq!(None, chi_squared(&[1.0, 2.0], &[1.0]));
+    assert_eq!(None, chi_squared(&[1.0, 2.0], &[1.0, -2.0]));
+    assert_eq!(None, chi_squared(&[1.0], &[f64::NAN]));
+}
+
+/// One-way ANOVA F-statistic of `groups` of input values:
+/// the between-group mean square over the within-group
+/// mean square, with `k - 1` and `n - k` degrees of freedom
+/// for `k` groups of `n` values in total. It is near 0.0
+/// when the group means agree and grows as they separate.
+/// The statistic is undefined for fewer than two groups, an
+/// empty group, no more values than groups, or zero
+/// within-group variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, anova_f(&[vec![1.0, 2.0]]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(8.0), anova_f(&[vec![0.0, 2.0], vec![4.0, 6.0]]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn anova_f(groups: &[Vec<f64>]) -> Option<f64> {
+    let k = groups.len();
+    let n: usize = groups.iter().map(|group| group.len()).sum();
+    if k < 2 || n <= k {
+        return None;
+    }
+    let grand = mean(&groups.concat())?;
+    let mut between = 0.0;
+    let mut within = 0.0;
+    for group in groups {
+        let deviation = mean(group)? - grand;
+        between += group.len() as f64 * deviation * deviation;
+        within += sum_sq_dev(group)?;
+    }
+    if within == 0.0 {
+        return None;
+    }
+    Some((between / (k - 1) as f64) / (within / (n - k) as f64))
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_anova_f_identical() {
+    let group = vec![75.5, 100.5, 95.5, 265.5, -37.0];
+    let f = anova_f(&[group.clone(), group.clone(), group]).unwrap();
+    assert!(f.abs() < 1e-12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_anova_f_separated() {
+    let groups = [
+        vec![1.0, 1.2, 0.9, 1.1],
+        vec![5.0, 5.3, 4.8],
+        vec![9.9, 10.1, 10.0, 10.2, 9.8],
+    ];
+    assert!(anova_f(&groups).unwrap() > 1000.0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_anova_f_invalid() {
+    assert_eq!(None, anova_f(&[]));
+    assert_eq!(None, anova_f(&[vec![1.0], vec![2.0]]));
+    assert_eq!(None, anova_f(&[vec![1.0, 2.0], vec![]]));
+    assert_eq!(None, anova_f(&[vec![1.0, 1.0], vec![2.0, 2.0]]));
+}
+
+/// Indices of the peaks (local maxima) of input values
+/// whose prominence exceeds `min_prominence`. A peak's
+/// prominence is its height above the higher of the lowest
+/// values either side of it before a higher value (or the
+/// end of the list) is reached. The first and last values
+/// are never peaks, and a flat top is reported at its first
+/// index. `NaN` values are never peaks.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(peaks(&[3.0, 2.0, 1.0], 0.0).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![1, 5], peaks(&[0.0, 4.0, 1.0, 1.5, 1.0, 3.0, 0.0], 1.0));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn peaks(nums: &[f64], min_prominence: f64) -> Vec<usize> {
+    let mut found = Vec::new();
+    let mut i = 1;
+    while i + 1 < nums.len() {
+        let height = nums[i];
+        // A rise is needed, which NaN on either side is not.
+        if nums[i - 1] >= height || nums[i - 1].is_nan() || height.is_nan() {
+            i += 1;
+            continue;
+        }
+        // Step over a flat top to the next differing value.
+        let mut end = i + 1;
+        while end < nums.len() && nums[end] == height {
+            end += 1;
+        }
+        if end < nums.len() && nums[end] < height {
+            let left = nums[..i]
+                .iter()
+                .rev()
+                .take_while(|&&val| val <= height || val.is_nan())
+                .fold(height, |acc, &val| acc.min(val));
+            let right = nums[end..]
+                .iter()
+                .take_while(|&&val| val <= height || val.is_nan())
+                .fold(height, |acc, &val| acc.min(val));
+            if height - left.max(right) > min_prominence {
+                found.push(i);
+            }
+        }
+        i = end;
+    }
+    found
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_peaks_noisy_sine() {
+    // Two and a half periods of a sine, with a small
+    // zig-zag of noise making many tiny local maxima.
+    let nums: Vec<f64> = (0..100)
+        .map(|i| {
+            let noise = if i % 2 == 0 { 0.05 } else { -0.05 };
+            (2.0 * std::f64::consts::PI * f64::from(i) / 40.0).sin() + noise
+        })
+        .collect();
+    assert!(peaks(&nums, 0.0).len() > 10);
+    let found = peaks(&nums, 0.5);
+    assert_eq!(3, found.len());
+    for (&index, expected) in found.iter().zip(&[10, 50, 90]) {
+        assert!((index as i32 - expected).abs() <= 1);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_peaks_flat_and_edges() {
+    assert_eq!(vec![2], peaks(&[0.0, 1.0, 2.0, 2.0, 2.0, 1.0], 0.5));
+    // A rise to the end is not a peak.
+    assert!(peaks(&[0.0, 1.0, 2.0, 2.0], 0.0).is_empty());
+    assert!(peaks(&[1.0, f64::NAN, 1.0], 0.0).is_empty());
+    assert!(peaks(&[], 0.0).is_empty());
+}
+
+/// Full discrete convolution of `signal` with `kernel`:
+/// element `n` of the result is the sum of
+/// `signal[i] * kernel[n - i]`, giving
+/// `signal.len() + kernel.len() - 1` results, or none if
+/// either list is empty.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(convolve(&[1.0, 2.0], &[]).is_empty());
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(vec![1.0, 3.0, 2.0], convolve(&[1.0, 2.0], &[1.0, 1.0]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn convolve(signal: &[f64], kernel: &[f64]) -> Vec<f64> {
+    if signal.is_empty() || kernel.is_empty() {
+        return Vec::new();
+    }
+    let mut result = vec![0.0; signal.len() + kernel.len() - 1];
+    for (i, s) in signal.iter().enumerate() {
+        for (j, k) in kernel.iter().enumerate() {
+            result[i + j] += s * k;
+        }
+    }
+    result
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_convolve_known() {
+    assert_eq!(
+        vec![0.0, 1.0, 2.5, 4.0, 1.5],
+        convolve(&[1.0, 2.0, 3.0], &[0.0, 1.0, 0.5])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_convolve_commutes() {
+    let signal = [75.5, 100.5, 95.5, 265.5, -37.0];
+    let kernel = [0.25, 0.5, 0.25];
+    assert_eq!(convolve(&signal, &kernel), convolve(&kernel, &signal));
+    assert_eq!(vec![2.0, 4.0], convolve(&[2.0], &[1.0, 2.0]));
+    assert!(convolve(&[], &kernel).is_empty());
+}
+
+/// Number of input values that are not finite: `NaN` or
+/// infinite. This and `count_finite` together count every
+/// input value.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(2, count_nan(&[1.0, f64::NAN, f64::INFINITY]));
+/// ```
+pub fn count_nan(nums: &[f64]) -> usize {
+    nums.iter().filter(|num| !num.is_finite()).count()
+}
+
+/// Number of input values that are finite: neither `NaN`
+/// nor infinite. This and `count_nan` together count every
+/// input value.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(1, count_finite(&[1.0, f64::NAN, f64::INFINITY]));
+/// ```
+pub fn count_finite(nums: &[f64]) -> usize {
+    nums.iter().filter(|num| num.is_finite()).count()
+}
+
+#[test]
+fn test_count_mixed() {
+    let nums = [
+        1.0,
+        f64::NAN,
+        -0.0,
+        f64::INFINITY,
+        f64::NAN,
+        f64::NEG_INFINITY,
+        f64::MAX,
+        f64::NAN,
+    ];
+    assert_eq!(5, count_nan(&nums));
+    assert_eq!(3, count_finite(&nums));
+    assert_eq!(nums.len(), count_nan(&nums) + count_finite(&nums));
+}
+
+#[test]
+fn test_count_empty() {
+    assert_eq!(0, count_nan(&[]));
+    assert_eq!(0, count_finite(&[]));
+}
+
+/// Input values with every `NaN` removed, keeping the rest
+/// (including infinities) in order. This prepares input for
+/// statistics such as `median` that are undefined when
+/// given `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median(&[1.0, f64::NAN, 2.0]));
+/// assert_eq!(Some(1.0), median(&drop_nan(&[1.0, f64::NAN, 2.0])));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn drop_nan(nums: &[f64]) -> Vec<f64> {
+    nums.iter().copied().filter(|num| !num.is_nan()).collect()
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_drop_nan_mixed() {
+    assert_eq!(vec![1.0, 2.0], drop_nan(&[1.0, f64::NAN, 2.0]));
+    assert_eq!(
+        vec![f64::INFINITY, -3.0],
+        drop_nan(&[f64::NAN, f64::INFINITY, f64::NAN, -3.0])
+    );
+    assert!(drop_nan(&[f64::NAN]).is_empty());
+}
+
+/// Input values with each clamped to `[lo, hi]`, clipping
+/// extreme values before aggregating. `NaN` values stay
+/// `NaN`. The result is undefined if `lo > hi` or either
+/// bound is `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, clamp_to_range(&[1.0], 2.0, 1.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![0.0, 0.5, 1.0]), clamp_to_range(&[-3.0, 0.5, 8.0], 0.0, 1.0));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn clamp_to_range(nums: &[f64], lo: f64, hi: f64) -> Option<Vec<f64>> {
+    if lo.is_nan() || hi.is_nan() || lo > hi {
+        return None;
+    }
+    Some(nums.iter().map(|num| num.clamp(lo, hi)).collect())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_clamp_to_range_extremes() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    let clamped = clamp_to_range(&nums, 0.0, 100.0).unwrap();
+    assert_eq!(vec![75.5, 100.0, 95.5, 100.0, 0.0], clamped);
+    assert_eq!(Some(vec![2.0, 2.0]), clamp_to_range(&[1.0, 3.0], 2.0, 2.0));
+    assert!(clamp_to_range(&[f64::NAN], 0.0, 1.0).unwrap()[0].is_nan());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_clamp_to_range_invalid() {
+    assert_eq!(None, clamp_to_range(&[1.0], 1.0, 0.0));
+    assert_eq!(None, clamp_to_range(&[1.0], f64::NAN, 1.0));
+    assert_eq!(None, clamp_to_range(&[], 0.0, f64::NAN));
+}
+
+/// Index of the first minimum of input values, skipping
+/// `NaN`. The index is undefined for an empty list, or a
+/// list that is all `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, argmin(&[f64::NAN]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2), argmin(&[0.0, f64::NAN, -1.0, -1.0]));
+/// ```
+pub fn argmin(nums: &[f64]) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (i, &num) in nums.iter().enumerate() {
+        match best {
+            _ if num.is_nan() => {}
+            Some(b) if nums[b] <= num => {}
+            _ => best = Some(i),
+        }
+    }
+    best
+}
+
+#[test]
+fn test_argmin_unique() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    assert_eq!(Some(4), argmin(&nums));
+    assert_eq!(min(&nums), argmin(&nums).map(|i| nums[i]));
+}
+
+#[test]
+fn test_argmin_leading_tie() {
+    assert_eq!(Some(0), argmin(&[1.0, 2.0, 1.0, 3.0]));
+    assert_eq!(Some(1), argmin(&[f64::NAN, 1.0, 1.0]));
+    assert_eq!(None, argmin(&[]));
+}
+
+/// Index of the first maximum of input values, skipping
+/// `NaN`. The index is undefined for an empty list, or a
+/// list that is all `NaN`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, argmax(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2), argmax(&[0.0, f64::NAN, 1.0, 1.0]));
+/// ```
+pub fn argmax(nums: &[f64]) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (i, &num) in nums.iter().enumerate() {
+        match best {
+            _ if num.is_nan() => {}
+            Some(b) if nums[b] >= num => {}
+            _ => best = Some(i),
+        }
+    }
+    best
+}
+
+#[test]
+fn test_argmax_unique() {
+    let nums = [75.5, 100.5, 95.5, 265.5, -37.0];
+    assert_eq!(Some(3), argmax(&nums));
+    assert_eq!(max(&nums), argmax(&nums).map(|i| nums[i]));
+}
+
+#[test]
+fn test_argmax_leading_tie() {
+    assert_eq!(Some(0), argmax(&[3.0, 2.0, 3.0, 1.0]));
+    assert_eq!(Some(1), argmax(&[f64::NAN, 3.0, 3.0]));
+    assert_eq!(None, argmax(&[f64::NAN, f64::NAN]));
+}
+
+/// Mode of continuous input values: the location of the
+/// peak of their Gaussian kernel density estimate with the
+/// given `bandwidth`, searched for on an even grid of 512
+/// points from the minimum to the maximum value. The mode
+/// is undefined for an empty list, a list with a value that
+/// is not finite, or a `bandwidth` that is not positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, density_mode(&[1.0, 2.0], 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// let mode = density_mode(&[0.0, 1.0, 1.0, 2.0], 0.5).unwrap();
+/// assert!((mode - 1.0).abs() < 0.01);
+/// ```
+#[cfg(feature = "std")]
+pub fn density_mode(nums: &[f64], bandwidth: f64) -> Option<f64> {
+    const GRID: usize = 512;
+    if bandwidth.is_nan() || bandwidth <= 0.0 {
+        return None;
+    }
+    if nums.iter().any(|num| !num.is_finite()) {
+        return None;
+    }
+    let lo = min(nums)?;
+    let hi = max(nums)?;
+    let step = (hi - lo) / (GRID - 1) as f64;
+    // The density's constant factor does not move its peak,
+    // so it is left out.
+    let density = |at: f64| {
+        nums.iter()
+            .map(|num| {
+                let z = (at - num) / bandwidth;
+                (-z * z / 2.0).exp()
+            })
+            .sum::<f64>()
+    };
+    let mut best = (lo, density(lo));
+    for i in 1..GRID {
+        let at = lo + step * i as f64;
+        let height = density(at);
+        if height > best.1 {
+            best = (at, height);
+        }
+    }
+    Some(best.0)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_density_mode_bimodal() {
+    // A taller cluster about 2 and a shorter one about 10.
+    let mut nums: Vec<f64> = (0..30).map(|i| 2.0 + f64::from(i % 7 - 3) * 0.1).collect();
+    nums.extend((0..12).map(|i| 10.0 + f64::from(i % 5 - 2) * 0.1));
+    let mode = density_mode(&nums, 0.5).unwrap();
+    assert!((mode - 2.0).abs() < 0.1);
+    // Taking away most of the first cluster moves the mode.
+    let mode = density_mode(&nums[25..], 0.5).unwrap();
+    assert!((mode - 10.0).abs() < 0.1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_density_mode_degenerate() {
+    assert_eq!(Some(3.0), density_mode(&[3.0, 3.0], 1.0));
+    assert_eq!(None, density_mode(&[], 1.0));
+    assert_eq!(None, density_mode(&[1.0, f64::NAN], 1.0));
+    assert_eq!(None, density_mode(&[1.0, f64::INFINITY], 1.0));
+    assert_eq!(None, density_mode(&[1.0, 2.0], f64::NAN));
 }